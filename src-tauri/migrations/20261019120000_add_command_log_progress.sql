@@ -0,0 +1 @@
+ALTER TABLE git_command_log ADD COLUMN progress TEXT;
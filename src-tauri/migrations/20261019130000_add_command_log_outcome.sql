@@ -0,0 +1 @@
+ALTER TABLE git_command_log ADD COLUMN outcome INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,5 @@
+ALTER TABLE git_command_log ADD COLUMN exit_code INTEGER;
+ALTER TABLE git_command_log ADD COLUMN duration_ms INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE git_command_log ADD COLUMN working_directory TEXT;
+ALTER TABLE git_command_log ADD COLUMN stdout TEXT;
+ALTER TABLE git_command_log ADD COLUMN stderr TEXT;
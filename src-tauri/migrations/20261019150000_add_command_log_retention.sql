@@ -0,0 +1,2 @@
+ALTER TABLE repository ADD COLUMN git_command_log_retention INTEGER NOT NULL DEFAULT 200;
+CREATE INDEX IF NOT EXISTS git_command_log_repository_created_at ON git_command_log (repository_id, created_at, id);
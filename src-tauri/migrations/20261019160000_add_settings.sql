@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS settings (
+  id INTEGER PRIMARY KEY NOT NULL,
+  git_command_log_retention INTEGER NOT NULL DEFAULT 200,
+  graph_page_size INTEGER NOT NULL DEFAULT 500,
+  auto_fetch_interval_minutes INTEGER DEFAULT 10,
+  diff_layout INTEGER NOT NULL DEFAULT 0,
+  max_highlight_size INTEGER NOT NULL DEFAULT 1048576,
+  max_image_size INTEGER NOT NULL DEFAULT 10485760,
+  git_executable TEXT,
+  git_query_timeout_seconds INTEGER DEFAULT 60,
+  git_mutation_timeout_seconds INTEGER
+);
+
+INSERT OR IGNORE INTO settings (id) VALUES (0);
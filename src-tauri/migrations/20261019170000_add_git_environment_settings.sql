@@ -0,0 +1,2 @@
+ALTER TABLE settings ADD COLUMN git_environment TEXT NOT NULL DEFAULT '[]';
+ALTER TABLE settings ADD COLUMN git_config_overrides TEXT NOT NULL DEFAULT '[]';
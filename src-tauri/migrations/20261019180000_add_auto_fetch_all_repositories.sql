@@ -0,0 +1 @@
+ALTER TABLE settings ADD COLUMN auto_fetch_all_repositories BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,3 @@
+ALTER TABLE repository ADD COLUMN ahead INTEGER;
+ALTER TABLE repository ADD COLUMN behind INTEGER;
+ALTER TABLE repository ADD COLUMN last_checked_at DATETIME;
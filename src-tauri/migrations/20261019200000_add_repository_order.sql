@@ -0,0 +1,4 @@
+ALTER TABLE repository ADD COLUMN pinned BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE repository ADD COLUMN sort_order INTEGER NOT NULL DEFAULT 0;
+
+UPDATE repository SET sort_order = id;
@@ -0,0 +1 @@
+ALTER TABLE settings ADD COLUMN protected_branches TEXT NOT NULL DEFAULT '["main","master","develop","release/*"]';
@@ -0,0 +1 @@
+ALTER TABLE repository ADD COLUMN main_repository_id INTEGER REFERENCES repository(id) ON DELETE SET NULL;
@@ -0,0 +1,139 @@
+use std::{collections::HashMap, time::Duration};
+
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::{
+    cli::{GitCommand, GitCommandRegistry, GitError},
+    db::{Db, GitCommandType, Repository},
+};
+
+/// How often to check whether a repository is due to be fetched
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+/// Longest time to wait between attempts when fetching keeps failing
+const MAX_BACKOFF: chrono::TimeDelta = chrono::TimeDelta::hours(1);
+/// Fetches that take longer are killed, so an unreachable remote can't block fetching for good
+const FETCH_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// Emitted after a repository is fetched in the background
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct RepositoryFetchedEvent {
+    pub repository_id: i64,
+}
+
+/// Consecutive failed fetches of a repository
+struct Failures {
+    count: u32,
+    last_attempt: NaiveDateTime,
+}
+
+/// Start fetching repositories in the background, on the interval set in the settings
+pub fn start(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut failures = HashMap::new();
+        loop {
+            tokio::time::sleep(CHECK_INTERVAL).await;
+            fetch_due_repositories(&app_handle, &mut failures).await;
+        }
+    });
+}
+
+async fn fetch_due_repositories(
+    app_handle: &tauri::AppHandle,
+    failures: &mut HashMap<i64, Failures>,
+) {
+    let db = app_handle.state::<Db>();
+    let settings = db.get_settings().await;
+    let Some(interval) = settings
+        .auto_fetch_interval_minutes
+        .map(chrono::TimeDelta::minutes)
+    else {
+        return;
+    };
+
+    let repositories = if settings.auto_fetch_all_repositories {
+        // Runs in the background, so there's no command to return the error from
+        match db.get_repositories().await {
+            Ok(repositories) => repositories,
+            Err(err) => {
+                eprintln!("Failed to get repositories to auto fetch: {err}");
+                return;
+            }
+        }
+    } else {
+        db.state
+            .lock()
+            .await
+            .open_repository
+            .clone()
+            .into_iter()
+            .collect()
+    };
+
+    for repository in repositories {
+        // Don't get in the way of changes the user is making
+        if app_handle
+            .state::<GitCommandRegistry>()
+            .is_mutation_running()
+        {
+            return;
+        }
+
+        let now = Utc::now().naive_utc();
+        let is_due = match failures.get(&repository.id) {
+            // Double the wait after each failure
+            Some(failures) => {
+                let backoff = interval
+                    .checked_mul(2_i32.saturating_pow(failures.count))
+                    .unwrap_or(MAX_BACKOFF)
+                    .min(MAX_BACKOFF.max(interval));
+                failures.last_attempt + backoff <= now
+            }
+            None => repository
+                .last_fetched_at
+                .is_none_or(|last_fetched_at| last_fetched_at + interval <= now),
+        };
+        if !is_due {
+            continue;
+        }
+
+        match fetch(app_handle, &db, &repository).await {
+            Ok(()) => {
+                failures.remove(&repository.id);
+                RepositoryFetchedEvent {
+                    repository_id: repository.id,
+                }
+                .emit(app_handle)
+                .expect("Failed to emit event");
+            }
+            Err(_) => {
+                let failures = failures.entry(repository.id).or_insert(Failures {
+                    count: 0,
+                    last_attempt: now,
+                });
+                failures.count += 1;
+                failures.last_attempt = now;
+            }
+        }
+    }
+}
+
+async fn fetch(
+    app_handle: &tauri::AppHandle,
+    db: &Db,
+    repository: &Repository,
+) -> Result<(), GitError> {
+    GitCommand::new("fetch")
+        .arg("--all")
+        .repository(repository)
+        .non_interactive()
+        .timeout(FETCH_TIMEOUT)
+        .run(app_handle, GitCommandType::Mutation)
+        .await?;
+    db.set_last_fetched_at(repository.id).await?;
+
+    Ok(())
+}
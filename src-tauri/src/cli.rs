@@ -1,13 +1,35 @@
-use std::io;
+use std::{
+    collections::HashMap,
+    future, io,
+    process::{Output, Stdio},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
 
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use tauri::Manager;
 use tauri_specta::Event;
 use thiserror::Error;
-use tokio::process::Command;
+use tokio::{io::AsyncReadExt, process::Command, sync::oneshot};
 
-use crate::db::{Db, GitCommandLog, GitCommandType};
+use crate::{
+    db::{
+        truncate_output, Db, GitCommandLog, GitCommandOutcome, GitCommandType, NewGitCommandLog,
+        Repository,
+    },
+    settings::Settings,
+    structures::{
+        git_progress::GitProgress,
+        git_version::{GitVersion, MINIMUM_GIT_VERSION},
+    },
+};
+
+/// Used to give each command that's run a unique id
+static NEXT_COMMAND_ID: AtomicU64 = AtomicU64::new(0);
 
 #[derive(Error, Debug)]
 pub enum GitError {
@@ -21,14 +43,96 @@ pub enum GitError {
     NoRepoOpen,
     #[error("Not a valid repository")]
     NotARepository,
+    #[error("Git command was cancelled")]
+    Cancelled,
+    #[error("Git command timed out after {0} seconds")]
+    TimedOut(u64),
+    #[error("Git can't be used: {0}")]
+    Unavailable(String),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
 pub struct GitCommandEvent(GitCommandLog);
 
+/// Emitted while a command started with [`GitCommand::progress`] is running
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct GitProgressEvent {
+    pub command_id: u64,
+    pub progress: GitProgress,
+}
+
+/// A command that's currently running, which can be cancelled
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct RunningGitCommand {
+    pub id: u64,
+    pub command: String,
+    pub command_type: GitCommandType,
+}
+
+/// Keeps track of running commands so they can be cancelled
+#[derive(Default)]
+pub struct GitCommandRegistry {
+    running: Mutex<HashMap<u64, (RunningGitCommand, oneshot::Sender<()>)>>,
+}
+
+impl GitCommandRegistry {
+    pub fn running(&self) -> Vec<RunningGitCommand> {
+        let mut running: Vec<_> = self
+            .running
+            .lock()
+            .unwrap()
+            .values()
+            .map(|(command, _)| command.clone())
+            .collect();
+        running.sort_by_key(|command| command.id);
+        running
+    }
+
+    pub fn is_mutation_running(&self) -> bool {
+        self.running
+            .lock()
+            .unwrap()
+            .values()
+            .any(|(command, _)| matches!(command.command_type, GitCommandType::Mutation))
+    }
+
+    /// Kill a running command. Returns false if the command isn't running.
+    pub fn cancel(&self, id: u64) -> bool {
+        match self.running.lock().unwrap().remove(&id) {
+            Some((_, cancel)) => cancel.send(()).is_ok(),
+            None => false,
+        }
+    }
+}
+
+/// Removes a command from the registry when it finishes, however that happens
+struct RegistryGuard<'a> {
+    registry: &'a GitCommandRegistry,
+    id: u64,
+}
+
+impl Drop for RegistryGuard<'_> {
+    fn drop(&mut self) {
+        self.registry.running.lock().unwrap().remove(&self.id);
+    }
+}
+
 pub struct GitCommand {
     command: String,
     args: Vec<String>,
+    progress: bool,
+    /// Id of the repository (None outside of a repository) and the directory to run in,
+    /// if not the open repository
+    location: Option<(Option<i64>, String)>,
+    envs: Vec<(String, String)>,
+    /// Overrides the timeout from the settings
+    timeout: Option<Duration>,
+    /// Whether to add the command to the repository's command log
+    log: bool,
+    /// Whether to keep the output in the command log when the command succeeds
+    log_output: bool,
+    /// Exit codes other than 0 that don't mean the command failed
+    allowed_exit_codes: Vec<i32>,
 }
 
 impl GitCommand {
@@ -36,9 +140,75 @@ impl GitCommand {
         Self {
             command: command.into(),
             args: Vec::default(),
+            progress: false,
+            location: None,
+            envs: Vec::new(),
+            timeout: None,
+            log: true,
+            log_output: false,
+            allowed_exit_codes: Vec::new(),
         }
     }
 
+    /// Run in a repository other than the open one, e.g. when fetching in the background
+    pub fn repository(&mut self, repository: &Repository) -> &mut Self {
+        self.location = Some((Some(repository.id), repository.local_path.clone()));
+        self
+    }
+
+    /// Run in a directory that isn't a repository, e.g. when cloning.
+    /// These commands aren't added to the command log, as there's no repository to log them to.
+    pub fn current_dir(&mut self, path: impl ToString) -> &mut Self {
+        self.location = Some((None, path.to_string()));
+        self
+    }
+
+    /// Don't add the command to the command log, for frequent background commands that would
+    /// push the user's own commands out of it, or commands whose output contains credentials
+    pub fn skip_log(&mut self) -> &mut Self {
+        self.log = false;
+        self
+    }
+
+    /// Keep the output in the command log even when the command succeeds. Only the output of
+    /// failed commands is kept otherwise, as queries can output whole files.
+    pub fn log_output(&mut self) -> &mut Self {
+        self.log_output = true;
+        self
+    }
+
+    /// Treat `code` as success, for commands that use it to mean there's nothing to output,
+    /// like `git config --get-regexp` when nothing matches
+    pub fn allow_exit_code(&mut self, code: i32) -> &mut Self {
+        self.allowed_exit_codes.push(code);
+        self
+    }
+
+    /// Stream progress events while the command runs, for long running commands like fetch
+    pub fn progress(&mut self) -> &mut Self {
+        self.progress = true;
+        self.arg("--progress")
+    }
+
+    /// Set an environment variable, overriding any from the settings
+    pub fn env(&mut self, key: &str, value: &str) -> &mut Self {
+        self.envs.push((key.into(), value.into()));
+        self
+    }
+
+    /// Fail instead of prompting for credentials, for commands run in the background
+    /// where nobody can answer the prompt
+    pub fn non_interactive(&mut self) -> &mut Self {
+        self.env("GIT_TERMINAL_PROMPT", "0")
+            .env("GCM_INTERACTIVE", "never")
+    }
+
+    /// Kill the command after `timeout`, instead of the timeout set for its type in the settings
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
     pub fn arg(&mut self, arg: impl ToString) -> &mut Self {
         self.args.push(arg.to_string());
         self
@@ -64,40 +234,303 @@ impl GitCommand {
         app_handle: &tauri::AppHandle,
         command_type: GitCommandType,
     ) -> Result<String, GitError> {
+        Ok(String::from_utf8_lossy(&self.run_bytes(app_handle, command_type).await?).into())
+    }
+
+    /// Run the command and return the raw bytes written to stdout.
+    ///
+    /// Use this instead of [`Self::run`] when the output may contain file contents,
+    /// which aren't guaranteed to be utf8.
+    pub async fn run_bytes(
+        &self,
+        app_handle: &tauri::AppHandle,
+        command_type: GitCommandType,
+    ) -> Result<Vec<u8>, GitError> {
         let db = app_handle.state::<Db>();
-        let local_path = db
-            .state
-            .lock()
-            .await
-            .open_repository
-            .as_ref()
-            .map(|repo| repo.local_path.clone())
-            .ok_or(GitError::NoRepoOpen)?;
+        let ((repository_id, local_path), mut cmd, timeout) = {
+            let state = db.state.lock().await;
+            if let Some(error) = state
+                .git_version
+                .as_ref()
+                .and_then(|check| check.error.clone())
+            {
+                return Err(GitError::Unavailable(error));
+            }
+            let location = match &self.location {
+                Some(location) => location.clone(),
+                None => state
+                    .open_repository
+                    .as_ref()
+                    .map(|repo| (Some(repo.id), repo.local_path.clone()))
+                    .ok_or(GitError::NoRepoOpen)?,
+            };
+            let timeout = self.timeout.or(match command_type {
+                GitCommandType::Query => state.settings.git_query_timeout_seconds,
+                GitCommandType::Mutation => state.settings.git_mutation_timeout_seconds,
+            }
+            .map(|seconds| Duration::from_secs(seconds as u64)));
+            (location, git_command(&state.settings), timeout)
+        };
+        let repository_id = repository_id.filter(|_| self.log);
 
-        let mut cmd = Command::new("git");
         cmd.arg(&self.command);
         for arg in self.args.iter() {
             cmd.arg(arg);
         }
-        cmd.current_dir(local_path);
-        let output = cmd.output().await?;
-        if !output.status.success() {
+        cmd.envs(self.envs.iter().cloned());
+        cmd.current_dir(&local_path);
+        // Nothing is written to stdin, so git shouldn't wait on input from it
+        cmd.stdin(Stdio::null());
+        // Dropping the running command when it's cancelled or times out will kill it
+        cmd.kill_on_drop(true);
+
+        let command_id = NEXT_COMMAND_ID.fetch_add(1, Ordering::Relaxed);
+        let command = format!("{} {}", &self.command, self.args.join(" "));
+        let registry = app_handle.state::<GitCommandRegistry>();
+        let (cancel, cancelled) = oneshot::channel();
+        registry.running.lock().unwrap().insert(
+            command_id,
+            (
+                RunningGitCommand {
+                    id: command_id,
+                    command: command.clone(),
+                    command_type: command_type.clone(),
+                },
+                cancel,
+            ),
+        );
+        let _guard = RegistryGuard {
+            registry: &registry,
+            id: command_id,
+        };
+
+        let started_at = Instant::now();
+        let mut log = NewGitCommandLog {
+            command,
+            args: [&self.command]
+                .into_iter()
+                .chain(&self.args)
+                .cloned()
+                .collect(),
+            command_type,
+            progress: None,
+            outcome: GitCommandOutcome::Success,
+            exit_code: None,
+            duration: Duration::ZERO,
+            working_directory: local_path,
+            stdout: None,
+            stderr: None,
+        };
+
+        let output = async {
+            if self.progress {
+                output_with_progress(cmd, app_handle, command_id).await
+            } else {
+                Ok((cmd.output().await?, Vec::new()))
+            }
+        };
+        let (output, progress) = tokio::select! {
+            output = output => output?,
+            _ = cancelled => {
+                log.outcome = GitCommandOutcome::Cancelled;
+                log.duration = started_at.elapsed();
+                log_command(app_handle, &db, repository_id, log).await?;
+                return Err(GitError::Cancelled);
+            }
+            _ = async {
+                match timeout {
+                    Some(timeout) => tokio::time::sleep(timeout).await,
+                    None => future::pending().await,
+                }
+            } => {
+                log.outcome = GitCommandOutcome::TimedOut;
+                log.duration = started_at.elapsed();
+                log_command(app_handle, &db, repository_id, log).await?;
+                return Err(GitError::TimedOut(timeout.unwrap_or_default().as_secs()));
+            }
+        };
+
+        let success = output.status.success()
+            || output
+                .status
+                .code()
+                .is_some_and(|code| self.allowed_exit_codes.contains(&code));
+        log.duration = started_at.elapsed();
+        log.exit_code = output.status.code();
+        log.progress = (!progress.is_empty()).then(|| {
+            progress
+                .iter()
+                .map(|progress| progress.to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        });
+        if !success {
+            log.outcome = GitCommandOutcome::Failed;
+        }
+        if !success || self.log_output {
+            log.stdout = Some(truncate_output(&output.stdout));
+            log.stderr = Some(truncate_output(&output.stderr));
+        }
+        log_command(app_handle, &db, repository_id, log).await?;
+
+        if !success {
             return Err(GitError::Unsuccessful(
-                String::from_utf8(output.stderr).expect("Failed to parse error as utf8"),
+                String::from_utf8_lossy(&output.stderr).into(),
             ));
         }
 
-        // Log command and emit event
-        GitCommandEvent(
-            db.add_git_command_log(
-                format!("{} {}", &self.command, self.args.join(" ")),
-                command_type,
-            )
-            .await?,
-        )
+        Ok(output.stdout)
+    }
+}
+
+/// Create a command for the configured git executable, with the configured environment and config
+fn git_command(settings: &Settings) -> Command {
+    let mut cmd = Command::new(settings.git_executable.as_deref().unwrap_or("git"));
+    cmd.envs(
+        settings
+            .git_environment
+            .iter()
+            .map(|variable| (&variable.name, &variable.value)),
+    );
+    for config in settings.git_config_overrides.iter() {
+        cmd.arg("-c")
+            .arg(format!("{}={}", config.key, config.value));
+    }
+    cmd
+}
+
+/// Result of checking that the configured git executable can be used
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct GitVersionCheck {
+    pub executable: String,
+    /// None if git couldn't be run or the version couldn't be parsed
+    pub version: Option<GitVersion>,
+    pub minimum_version: GitVersion,
+    /// Why git can't be used, if it can't
+    pub error: Option<String>,
+}
+
+/// Run `git --version` and check that it meets [`MINIMUM_GIT_VERSION`]
+pub async fn check_git_version(settings: &Settings) -> GitVersionCheck {
+    let mut check = GitVersionCheck {
+        executable: settings.git_executable.clone().unwrap_or("git".into()),
+        version: None,
+        minimum_version: MINIMUM_GIT_VERSION,
+        error: None,
+    };
+
+    let output = match git_command(settings).arg("--version").output().await {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => {
+            check.error = Some(String::from_utf8_lossy(&output.stderr).trim().into());
+            return check;
+        }
+        Err(err) => {
+            check.error = Some(format!("Failed to run {}: {err}", check.executable));
+            return check;
+        }
+    };
+
+    match String::from_utf8_lossy(&output.stdout).parse::<GitVersion>() {
+        Ok(version) => {
+            check.version = Some(version);
+            if version < MINIMUM_GIT_VERSION {
+                check.error = Some(format!(
+                    "Git {version} is too old, version {MINIMUM_GIT_VERSION} or later is required"
+                ));
+            }
+        }
+        Err(err) => check.error = Some(err),
+    }
+
+    check
+}
+
+/// Log a command and emit an event so the frontend can show it
+async fn log_command(
+    app_handle: &tauri::AppHandle,
+    db: &Db,
+    repository_id: Option<i64>,
+    log: NewGitCommandLog,
+) -> Result<(), GitError> {
+    let Some(repository_id) = repository_id else {
+        return Ok(());
+    };
+    GitCommandEvent(db.add_git_command_log(repository_id, log).await?)
         .emit(app_handle)
         .expect("Failed to emit event");
+    Ok(())
+}
 
-        Ok(String::from_utf8(output.stdout).expect("Failed to parse as utf8"))
-    }
+/// Run a command while emitting its progress from stderr.
+///
+/// Returns the output with progress lines removed from stderr, and the final progress of each phase.
+async fn output_with_progress(
+    mut cmd: Command,
+    app_handle: &tauri::AppHandle,
+    command_id: u64,
+) -> Result<(Output, Vec<GitProgress>), GitError> {
+    let mut child = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+    let mut stdout = child.stdout.take().expect("Failed to take stdout");
+    let mut stderr = child.stderr.take().expect("Failed to take stderr");
+
+    let read_stdout = async {
+        let mut bytes = Vec::new();
+        stdout.read_to_end(&mut bytes).await?;
+        Ok::<_, io::Error>(bytes)
+    };
+    let read_stderr = async {
+        let mut messages = Vec::new();
+        let mut phases: Vec<GitProgress> = Vec::new();
+        let mut line = Vec::new();
+        let mut buf = [0; 1024];
+        loop {
+            let len = stderr.read(&mut buf).await?;
+            if len == 0 {
+                break;
+            }
+            // Progress lines are updated in place by ending them with a carriage return
+            for &byte in &buf[..len] {
+                if byte != b'\r' && byte != b'\n' {
+                    line.push(byte);
+                    continue;
+                }
+                match String::from_utf8_lossy(&line).parse::<GitProgress>() {
+                    Ok(progress) => {
+                        GitProgressEvent {
+                            command_id,
+                            progress: progress.clone(),
+                        }
+                        .emit(app_handle)
+                        .expect("Failed to emit event");
+
+                        match phases.last_mut() {
+                            Some(last) if last.phase == progress.phase => *last = progress,
+                            _ => phases.push(progress),
+                        }
+                    }
+                    Err(_) if !line.is_empty() => {
+                        messages.extend_from_slice(&line);
+                        messages.push(b'\n');
+                    }
+                    Err(_) => {}
+                }
+                line.clear();
+            }
+        }
+        messages.extend_from_slice(&line);
+        Ok::<_, io::Error>((messages, phases))
+    };
+
+    let (stdout, (stderr, phases)) = tokio::try_join!(read_stdout, read_stderr)?;
+    let status = child.wait().await?;
+
+    Ok((
+        Output {
+            status,
+            stdout,
+            stderr,
+        },
+        phases,
+    ))
 }
@@ -0,0 +1,33 @@
+use crate::{cli::GitCommand, db::GitCommandType};
+
+use super::CommandResult;
+
+/// Add a remote, fetching its branches straight away if `fetch` is set
+#[tauri::command]
+#[specta::specta]
+pub async fn add_remote(
+    app_handle: tauri::AppHandle,
+    name: String,
+    url: String,
+    fetch: bool,
+) -> CommandResult<()> {
+    GitCommand::new("remote")
+        .arg("add")
+        .arg("--")
+        .arg(&name)
+        .arg(url)
+        .run(&app_handle, GitCommandType::Mutation)
+        .await?;
+
+    // Fetched separately because `remote add --fetch` doesn't report progress
+    if fetch {
+        GitCommand::new("fetch")
+            .progress()
+            .arg("--end-of-options")
+            .arg(name)
+            .run(&app_handle, GitCommandType::Mutation)
+            .await?;
+    }
+
+    Ok(())
+}
@@ -1,26 +1,193 @@
-use std::path::PathBuf;
+use std::path::Path;
 
-use tokio::fs;
+use tauri::Manager;
+use tauri_specta::Event;
 
-use crate::{cli::GitError, commands::CommandResult, db::Db};
+use crate::{
+    cli::{GitCommand, GitError},
+    commands::{CommandError, CommandResult},
+    db::{Db, GitCommandType, Repository},
+    status_check::RepositoryStatusEvent,
+    structures::worktree::Worktree,
+    watcher::RepositoryWatcher,
+};
 
 #[tauri::command]
 #[specta::specta]
 pub async fn add_repository_from_path(
+    app_handle: tauri::AppHandle,
     db: tauri::State<'_, Db>,
+    watcher: tauri::State<'_, RepositoryWatcher>,
     local_path: String,
 ) -> CommandResult<()> {
-    let local_path = PathBuf::from(local_path);
+    register_and_open(&app_handle, &db, &watcher, Path::new(&local_path)).await?;
 
-    // Check that it's a valid git repository
-    fs::metadata(local_path.join(".git"))
-        .await
-        .ok()
-        .filter(|meta| meta.is_dir())
-        .ok_or(GitError::NotARepository)?;
+    Ok(())
+}
 
-    let repository = db.add_repository(local_path).await?;
+/// Add the repository that `path` is in, then open and watch it
+pub async fn register_and_open(
+    app_handle: &tauri::AppHandle,
+    db: &Db,
+    watcher: &RepositoryWatcher,
+    path: &Path,
+) -> CommandResult<Repository> {
+    let repository = register_repository(app_handle, db, path).await?;
     db.set_open_repository(Some(repository.id)).await?;
+    watcher.watch(app_handle, Some(&repository))?;
+
+    Ok(repository)
+}
+
+/// Add the repository that `path` is in without opening it, linked to its worktrees
+pub async fn register_repository(
+    app_handle: &tauri::AppHandle,
+    db: &Db,
+    path: &Path,
+) -> CommandResult<Repository> {
+    let root = resolve_repository_root(app_handle, path).await?;
+    let repository = db
+        .add_repository(&root.name, &root.local_path)
+        .await
+        .map_err(|err| CommandError::from_repository_path_error(err, &root.local_path))?;
+    link_worktrees(app_handle, db, &repository).await?;
+
+    Ok(db.get_repository(repository.id).await?)
+}
+
+/// Link the added worktrees of `repository`'s main repository to it, whether `repository` is the
+/// main worktree or one of the linked ones
+pub async fn link_worktrees(
+    app_handle: &tauri::AppHandle,
+    db: &Db,
+    repository: &Repository,
+) -> CommandResult<()> {
+    let output = GitCommand::new("worktree")
+        .repository(repository)
+        .arg("list")
+        .arg("--porcelain")
+        .run(app_handle, GitCommandType::Query)
+        .await?;
+    let worktrees = Worktree::from_porcelain(&output).map_err(CommandError::Parse)?;
+
+    // A bare main repository can't be added, so there's nothing to link to
+    let Some(main) = worktrees
+        .iter()
+        .find(|worktree| worktree.main && !worktree.bare)
+    else {
+        return Ok(());
+    };
+    let linked_paths: Vec<String> = worktrees
+        .iter()
+        .filter(|worktree| !worktree.main)
+        .map(|worktree| worktree.path.clone())
+        .collect();
+    db.link_worktrees(&main.path, &linked_paths).await?;
 
     Ok(())
 }
+
+/// Store repositories by the top level of their working tree, for those added before their path
+/// was resolved to it, so adding them again is caught as a duplicate
+pub async fn normalize_repository_paths(app_handle: &tauri::AppHandle) {
+    let db = app_handle.state::<Db>();
+    let watcher = app_handle.state::<RepositoryWatcher>();
+    let Ok(repositories) = db.get_repositories().await else {
+        return;
+    };
+
+    for repository in repositories {
+        // e.g. the repository was moved or deleted, which the user can fix by relocating it
+        let Ok(root) = resolve_repository_root(app_handle, Path::new(&repository.local_path)).await
+        else {
+            continue;
+        };
+        if root.local_path == repository.local_path {
+            continue;
+        }
+        // Fails if the top level was added as well, leaving both for the user to remove one
+        let Ok(updated) = db
+            .relocate_repository(repository.id, &root.local_path)
+            .await
+        else {
+            continue;
+        };
+
+        let open_repository = db.state.lock().await.open_repository.clone();
+        if open_repository
+            .as_ref()
+            .is_some_and(|repo| repo.id == repository.id)
+        {
+            // A failure is kept by the watcher for the frontend to show
+            let _ = watcher.watch(app_handle, open_repository.as_ref());
+        }
+        RepositoryStatusEvent(updated)
+            .emit(app_handle)
+            .expect("Failed to emit event");
+    }
+}
+
+/// The top level of a repository's working tree
+pub struct RepositoryRoot {
+    /// Name of the top level folder
+    pub name: String,
+    /// Absolute path of the top level folder
+    pub local_path: String,
+}
+
+/// Find the top level of the working tree that `path` is in, which can be a subfolder, a linked
+/// worktree or a submodule. Bare repositories are rejected, as they have no working tree.
+pub async fn resolve_repository_root(
+    app_handle: &tauri::AppHandle,
+    path: &Path,
+) -> CommandResult<RepositoryRoot> {
+    // Git would fail to start in a missing folder, which looks like git itself is missing
+    if !path.is_dir() {
+        return Err(CommandError::Other(format!(
+            "{} isn't a folder",
+            path.display()
+        )));
+    }
+    let rev_parse = |arg: &str| {
+        let mut command = GitCommand::new("rev-parse");
+        command
+            .current_dir(path.display())
+            // Git's messages are translated, so use the untranslated ones to match them
+            .env("LC_ALL", "C")
+            .arg(arg);
+        command
+    };
+
+    let is_bare = match rev_parse("--is-bare-repository")
+        .run(app_handle, GitCommandType::Query)
+        .await
+    {
+        Ok(output) => output.trim() == "true",
+        Err(GitError::Unsuccessful(stderr)) if stderr.contains("not a git repository") => {
+            return Err(GitError::NotARepository.into())
+        }
+        Err(err) => return Err(err.into()),
+    };
+    if is_bare {
+        return Err(CommandError::Other(format!(
+            "{} is a bare repository, which has no working tree. Add one of its worktrees instead.",
+            path.display()
+        )));
+    }
+
+    let local_path = rev_parse("--show-toplevel")
+        .run(app_handle, GitCommandType::Query)
+        .await?
+        .trim()
+        .to_string();
+
+    let name = Path::new(&local_path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| name.into())
+        .ok_or(CommandError::Other(format!(
+            "Couldn't get a folder name from {local_path}"
+        )))?;
+
+    Ok(RepositoryRoot { name, local_path })
+}
@@ -0,0 +1,60 @@
+use std::path::Path;
+
+use serde::Deserialize;
+use specta::Type;
+
+use crate::{
+    cli::{GitCommand, GitError},
+    db::{Db, GitCommandType},
+};
+
+use super::{add_repository_from_path::register_repository, CommandResult};
+
+#[derive(Debug, Deserialize, Type)]
+pub struct AddWorktreeOptions {
+    /// Create `branch` instead of checking out an existing one
+    pub create_branch: bool,
+    /// Where the new branch starts, defaults to HEAD
+    pub start_point: Option<String>,
+    /// Also add the worktree to the repository list
+    pub register: bool,
+}
+
+/// Check out `branch` in a new worktree at `path`, which is relative to the open repository
+#[tauri::command]
+#[specta::specta]
+pub async fn add_worktree(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    path: String,
+    branch: String,
+    options: AddWorktreeOptions,
+) -> CommandResult<()> {
+    let repository_path = db
+        .state
+        .lock()
+        .await
+        .open_repository
+        .as_ref()
+        .map(|repo| repo.local_path.clone())
+        .ok_or(GitError::NoRepoOpen)?;
+    let path = Path::new(&repository_path).join(path);
+
+    let mut command = GitCommand::new("worktree");
+    command.arg("add");
+    if options.create_branch {
+        command.arg("-b").arg(&branch).arg("--").arg(path.display());
+        if let Some(start_point) = options.start_point {
+            command.arg(start_point);
+        }
+    } else {
+        command.arg("--").arg(path.display()).arg(&branch);
+    }
+    command.run(&app_handle, GitCommandType::Mutation).await?;
+
+    if options.register {
+        register_repository(&app_handle, &db, &path).await?;
+    }
+
+    Ok(())
+}
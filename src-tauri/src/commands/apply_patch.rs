@@ -0,0 +1,73 @@
+use std::path::Path;
+
+use crate::{
+    cli::{GitCommand, GitError},
+    db::{Db, GitCommandType},
+    structures::patch::PatchApplyMethod,
+};
+
+use super::{CommandError, CommandResult};
+
+/// Apply a patch or mbox file. Check it first with `check_patch` to see why it might fail.
+#[tauri::command]
+#[specta::specta]
+pub async fn apply_patch(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    path: String,
+    method: PatchApplyMethod,
+) -> CommandResult<()> {
+    match method {
+        PatchApplyMethod::Apply => {
+            GitCommand::new("apply")
+                .arg(path)
+                .run(&app_handle, GitCommandType::Mutation)
+                .await?;
+        }
+        PatchApplyMethod::Am => {
+            // Aborting below would throw away the user's own session
+            if am_in_progress(&app_handle, &db).await? {
+                return Err(CommandError::Other(
+                    "A mailbox or rebase is already being applied. Continue or abort it first."
+                        .into(),
+                ));
+            }
+
+            let result = GitCommand::new("am")
+                .arg(path)
+                .run(&app_handle, GitCommandType::Mutation)
+                .await;
+
+            // Don't leave the repository in the middle of applying the mailbox
+            if let Err(GitError::Unsuccessful(_)) = result {
+                GitCommand::new("am")
+                    .arg("--abort")
+                    .run(&app_handle, GitCommandType::Mutation)
+                    .await?;
+            }
+            result?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether `git am`, or a rebase that uses it, is in progress in the open repository
+async fn am_in_progress(app_handle: &tauri::AppHandle, db: &Db) -> CommandResult<bool> {
+    let local_path = db
+        .state
+        .lock()
+        .await
+        .open_repository
+        .as_ref()
+        .map(|repo| repo.local_path.clone())
+        .ok_or(GitError::NoRepoOpen)?;
+    // Relative to the top level of the working tree, unless the git directory is elsewhere
+    let state_dir = GitCommand::new("rev-parse")
+        .arg("--git-path")
+        .arg("rebase-apply")
+        .run(app_handle, GitCommandType::Query)
+        .await?;
+
+    Ok(Path::new(&local_path).join(state_dir.trim()).exists())
+}
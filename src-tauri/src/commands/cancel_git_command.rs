@@ -0,0 +1,18 @@
+use crate::cli::GitCommandRegistry;
+
+use super::{CommandError, CommandResult};
+
+#[tauri::command]
+#[specta::specta]
+pub async fn cancel_git_command(
+    registry: tauri::State<'_, GitCommandRegistry>,
+    id: u64,
+) -> CommandResult<()> {
+    if !registry.cancel(id) {
+        return Err(CommandError::Other(format!(
+            "Git command {id} is no longer running"
+        )));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use crate::{
+    cli::{GitCommand, GitError},
+    db::GitCommandType,
+    structures::patch::PatchCheck,
+};
+
+use super::CommandResult;
+
+/// Check whether a patch or mbox file applies cleanly to the working tree, without changing anything
+#[tauri::command]
+#[specta::specta]
+pub async fn check_patch(app_handle: tauri::AppHandle, path: String) -> CommandResult<PatchCheck> {
+    let result = GitCommand::new("apply")
+        .arg("--check")
+        .arg("--verbose") // Report the lines each failed hunk was searching for
+        .arg(path)
+        .run(&app_handle, GitCommandType::Query)
+        .await;
+
+    match result {
+        Ok(_) => Ok(PatchCheck::default()),
+        // Errors that aren't about hunks or files (e.g. an invalid patch) are returned as is
+        Err(GitError::Unsuccessful(stderr)) => match stderr.parse() {
+            Ok(check) => Ok(check),
+            Err(_) => Err(GitError::Unsuccessful(stderr).into()),
+        },
+        Err(err) => Err(err.into()),
+    }
+}
@@ -0,0 +1,183 @@
+use std::path::{self, Path, PathBuf};
+
+use serde::Deserialize;
+use specta::Type;
+
+use crate::{
+    cli::GitCommand,
+    commands::{add_repository_from_path::register_and_open, CommandError, CommandResult},
+    db::{Db, GitCommandType, Repository},
+    watcher::RepositoryWatcher,
+};
+
+#[derive(Debug, Deserialize, Type)]
+pub struct CloneOptions {
+    /// Branch to check out instead of the remote's HEAD
+    pub branch: Option<String>,
+    /// Only fetch this many commits of history
+    pub depth: Option<u32>,
+    /// Only fetch the history of the checked out branch
+    pub single_branch: bool,
+    pub recurse_submodules: bool,
+    /// Partial clone filter, e.g. `blob:none` to fetch file contents only when they're needed
+    pub filter: Option<String>,
+}
+
+/// Clone `source` (a URL or local path) into the new folder `destination`, then add and open it
+#[tauri::command]
+#[specta::specta]
+pub async fn clone_repository(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    watcher: tauri::State<'_, RepositoryWatcher>,
+    source: String,
+    destination: String,
+    options: CloneOptions,
+) -> CommandResult<Repository> {
+    let destination = absolute_path(&destination)?;
+    let (parent, args) = clone_args(&source, &destination, &options)?;
+
+    let mut command = GitCommand::new("clone");
+    command.current_dir(parent.display()).progress();
+    for arg in args {
+        command.arg(arg);
+    }
+    command.run(&app_handle, GitCommandType::Mutation).await?;
+
+    register_and_open(&app_handle, &db, &watcher, Path::new(&destination)).await
+}
+
+fn absolute_path(path: &str) -> CommandResult<PathBuf> {
+    path::absolute(path).map_err(|err| CommandError::Other(format!("Invalid path {path}: {err}")))
+}
+
+/// The folder to run `git clone` in and its arguments, for an absolute `destination`
+fn clone_args(
+    source: &str,
+    destination: &Path,
+    options: &CloneOptions,
+) -> CommandResult<(PathBuf, Vec<String>)> {
+    let parent = destination
+        .parent()
+        .filter(|parent| parent.is_dir())
+        .ok_or(CommandError::Other(format!(
+            "The folder to clone into doesn't exist: {}",
+            destination.display()
+        )))?;
+
+    // Git ignores --depth and --filter for local paths unless they're given as a URL
+    let source = if (options.depth.is_some() || options.filter.is_some())
+        && Path::new(source).is_dir()
+    {
+        let path = absolute_path(source)?;
+        url::Url::from_file_path(&path)
+            .map_err(|_| CommandError::Other(format!("Can't convert {} to a URL", path.display())))?
+            .to_string()
+    } else {
+        source.into()
+    };
+
+    let mut args = Vec::new();
+    if let Some(branch) = &options.branch {
+        args.push(format!("--branch={branch}"));
+    }
+    if let Some(depth) = options.depth {
+        args.push(format!("--depth={depth}"));
+    }
+    if options.single_branch {
+        args.push("--single-branch".into());
+    }
+    if options.recurse_submodules {
+        args.push("--recurse-submodules".into());
+    }
+    if let Some(filter) = &options.filter {
+        args.push(format!("--filter={filter}"));
+    }
+    args.push("--".into()); // Don't treat a source starting with `-` as an option
+    args.push(source);
+    args.push(destination.display().to_string());
+
+    Ok((parent.to_path_buf(), args))
+}
+
+#[cfg(test)]
+mod test {
+    use std::{path::Path, process::Command};
+
+    use super::{clone_args, CloneOptions};
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .current_dir(dir)
+            // Signing would fail or prompt on machines that sign commits by default
+            .args(["-c", "commit.gpgsign=false"])
+            .args(args)
+            .env("GIT_AUTHOR_NAME", "Test")
+            .env("GIT_AUTHOR_EMAIL", "test@example.com")
+            .env("GIT_COMMITTER_NAME", "Test")
+            .env("GIT_COMMITTER_EMAIL", "test@example.com")
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {args:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).trim().into()
+    }
+
+    #[test]
+    fn builds_args_that_clone_a_local_repository_with_depth_and_branch() {
+        let dir = tempfile::tempdir().unwrap();
+        git(
+            dir.path(),
+            &["init", "--bare", "--initial-branch=main", "origin.git"],
+        );
+        git(dir.path(), &["init", "--initial-branch=main", "work"]);
+        let work = dir.path().join("work");
+        for message in ["First", "Second", "Third"] {
+            git(&work, &["commit", "--allow-empty", "--message", message]);
+        }
+        git(&work, &["branch", "feature", "HEAD~1"]);
+        git(&work, &["push", "../origin.git", "main", "feature"]);
+
+        let destination = dir.path().join("clones").join("my clone");
+        std::fs::create_dir(destination.parent().unwrap()).unwrap();
+        let options = CloneOptions {
+            branch: Some("feature".into()),
+            depth: Some(1),
+            single_branch: false,
+            recurse_submodules: false,
+            filter: None,
+        };
+        let source = dir.path().join("origin.git");
+        let (parent, args) = clone_args(source.to_str().unwrap(), &destination, &options).unwrap();
+        assert_eq!(parent, dir.path().join("clones"));
+        assert!(args.iter().any(|arg| arg.starts_with("file:///")));
+
+        let args: Vec<&str> = args.iter().map(|arg| arg.as_str()).collect();
+        git(&parent, &[&["clone"], args.as_slice()].concat());
+
+        assert_eq!(git(&destination, &["branch", "--show-current"]), "feature");
+        assert_eq!(git(&destination, &["rev-list", "--count", "HEAD"]), "1");
+        assert_eq!(git(&destination, &["log", "--format=%s"]), "Second");
+    }
+
+    #[test]
+    fn rejects_a_missing_parent_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = CloneOptions {
+            branch: None,
+            depth: None,
+            single_branch: false,
+            recurse_submodules: false,
+            filter: None,
+        };
+        assert!(clone_args(
+            "../repo",
+            &dir.path().join("missing").join("clone"),
+            &options
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{
+    cli::GitCommand,
+    db::{Db, GitCommandType},
+    structures::{
+        commit::{Commit, LOG_FIELDS},
+        file::File,
+        hash::GitHash,
+        revision_comparison::{CompareMode, RevisionComparison},
+    },
+};
+
+use super::{CommandError, CommandResult};
+
+/// Compare two revisions, which can be anything git can resolve to a commit (e.g. branch names)
+#[tauri::command]
+#[specta::specta]
+pub async fn compare_revisions(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    base: String,
+    head: String,
+    mode: CompareMode,
+) -> CommandResult<RevisionComparison> {
+    let limit = db.get_settings().await.graph_page_size;
+    let base_hash = resolve_commit(&app_handle, &base).await?;
+    let head_hash = resolve_commit(&app_handle, &head).await?;
+
+    let src_hash = match mode {
+        CompareMode::TwoDot => base_hash.clone(),
+        CompareMode::ThreeDot => GitCommand::new("merge-base")
+            .arg(&base_hash.0)
+            .arg(&head_hash.0)
+            .run(&app_handle, GitCommandType::Query)
+            .await?
+            .trim()
+            .parse()
+            .map_err(CommandError::Parse)?,
+    };
+
+    let files = GitCommand::new("diff")
+        .arg(&src_hash.0)
+        .arg(&head_hash.0)
+        .arg("--raw") // Show a summary of changes without the full diffs
+        .arg("--abbrev=40") // Show full git hashes
+        .arg("-z") // Separate files with NULs for parsing
+        .run(&app_handle, GitCommandType::Query)
+        .await?;
+
+    let (base_commits, base_commits_truncated) =
+        unique_commits(&app_handle, &base_hash, &head_hash, limit).await?;
+    let (head_commits, head_commits_truncated) =
+        unique_commits(&app_handle, &head_hash, &base_hash, limit).await?;
+
+    Ok(RevisionComparison {
+        files: File::from_raw(&files).map_err(CommandError::Parse)?,
+        base_commits,
+        base_commits_truncated,
+        head_commits,
+        head_commits_truncated,
+        src_hash,
+        dst_hash: head_hash,
+    })
+}
+
+/// Get the full hash of the commit a revision points to
+async fn resolve_commit(app_handle: &tauri::AppHandle, revision: &str) -> CommandResult<GitHash> {
+    GitCommand::new("rev-parse")
+        .arg("--verify")
+        .arg("--end-of-options") // Don't treat revisions starting with `-` as options
+        .arg(format!("{revision}^{{commit}}"))
+        .run(app_handle, GitCommandType::Query)
+        .await?
+        .trim()
+        .parse()
+        .map_err(CommandError::Parse)
+}
+
+/// Get up to `limit` commits reachable from `from` but not from `exclude`, and whether there are more
+async fn unique_commits(
+    app_handle: &tauri::AppHandle,
+    from: &GitHash,
+    exclude: &GitHash,
+    limit: i64,
+) -> CommandResult<(Vec<Commit>, bool)> {
+    let format = GitCommand::create_format_arg(LOG_FIELDS, "%x00");
+    let commits = GitCommand::new("log")
+        .arg(format!("--format={format}\x01"))
+        .arg(format!("-{}", limit + 1)) // Get one extra commit to know if there are more
+        .arg(&from.0)
+        .arg(format!("^{}", exclude.0))
+        .run(app_handle, GitCommandType::Query)
+        .await?;
+
+    let mut commits = Commit::from_log(&commits).map_err(CommandError::Parse)?;
+    let truncated = commits.len() as i64 > limit;
+    commits.truncate(limit as usize);
+
+    Ok((commits, truncated))
+}
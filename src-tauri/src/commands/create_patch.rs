@@ -0,0 +1,30 @@
+use crate::{cli::GitCommand, db::GitCommandType};
+
+use super::{CommandError, CommandResult};
+
+/// Create a unified diff of changes in the working tree, or in the index if `staged`.
+/// Only includes the given paths, or all changes if `paths` is empty.
+#[tauri::command]
+#[specta::specta]
+pub async fn create_patch(
+    app_handle: tauri::AppHandle,
+    staged: bool,
+    paths: Vec<String>,
+) -> CommandResult<String> {
+    let mut command = GitCommand::new("diff");
+    command
+        .arg_if("--cached", staged)
+        .arg("--binary") // Include binary files so the patch can be applied
+        .arg("--");
+    for path in paths {
+        command.arg(path);
+    }
+
+    // Decoding changes to files that aren't utf8 lossily would stop the patch from applying
+    let patch = command
+        .run_bytes(&app_handle, GitCommandType::Query)
+        .await?;
+    String::from_utf8(patch).map_err(|_err| {
+        CommandError::Other("Can't create a patch of changes to files that aren't utf8".into())
+    })
+}
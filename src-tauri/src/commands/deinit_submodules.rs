@@ -0,0 +1,29 @@
+use crate::{cli::GitCommand, db::GitCommandType};
+
+use super::{CommandError, CommandResult};
+
+/// Remove the working trees and config of submodules, keeping them in `.gitmodules`.
+/// Without `force`, fails if a submodule has local changes.
+#[tauri::command]
+#[specta::specta]
+pub async fn deinit_submodules(
+    app_handle: tauri::AppHandle,
+    paths: Vec<String>,
+    force: bool,
+) -> CommandResult<()> {
+    // Git needs `--all` to deinit every submodule, so make that explicit instead
+    if paths.is_empty() {
+        return Err(CommandError::Other(
+            "Choose the submodules to deinitialize".into(),
+        ));
+    }
+
+    let mut command = GitCommand::new("submodule");
+    command.arg("deinit").arg_if("--force", force).arg("--");
+    for path in paths {
+        command.arg(path);
+    }
+    command.run(&app_handle, GitCommandType::Mutation).await?;
+
+    Ok(())
+}
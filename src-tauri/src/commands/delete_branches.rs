@@ -0,0 +1,68 @@
+use std::collections::HashSet;
+
+use crate::{
+    cli::GitCommand,
+    db::{Db, GitCommandType},
+    structures::branch_cleanup::BranchCleanupCandidate,
+};
+
+use super::{
+    get_branch_cleanup_candidates::{cleanup_branches, merged_branches},
+    CommandError, CommandResult,
+};
+
+/// Delete the confirmed local branches, refusing if any of them is checked out, protected, or
+/// neither merged into `base` nor gone from its remote. The candidates are checked again, as the
+/// branches may have changed since the preview.
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_branches(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    branches: Vec<String>,
+    base: String,
+) -> CommandResult<()> {
+    if branches.is_empty() {
+        return Ok(());
+    }
+
+    let protected_branches = db.get_settings().await.protected_branches;
+    let local_branches = cleanup_branches(&app_handle).await?;
+    for name in &branches {
+        let branch = local_branches
+            .iter()
+            .find(|branch| &branch.name == name)
+            .ok_or_else(|| CommandError::Other(format!("Branch \"{name}\" doesn't exist")))?;
+        if branch.is_protected(&protected_branches) {
+            return Err(CommandError::Other(format!(
+                "Branch \"{name}\" is checked out or protected"
+            )));
+        }
+    }
+
+    let merged = merged_branches(&app_handle, &base).await?;
+    let candidates = BranchCleanupCandidate::from_branches(
+        local_branches,
+        &merged.lines().collect::<HashSet<_>>(),
+        &base,
+        &protected_branches,
+    );
+    if let Some(name) = branches
+        .iter()
+        .find(|name| !candidates.iter().any(|candidate| &candidate.name == *name))
+    {
+        return Err(CommandError::Other(format!(
+            "Branch \"{name}\" isn't merged into \"{base}\" and its upstream isn't gone"
+        )));
+    }
+
+    // Git only checks if branches are merged into HEAD or their upstream, which isn't the base
+    let mut command = GitCommand::new("branch");
+    command.arg("-D").arg("--");
+    for name in branches {
+        command.arg(name);
+    }
+    command.run(&app_handle, GitCommandType::Mutation).await?;
+
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use serde::Deserialize;
+use specta::Type;
+
+use crate::{
+    cli::GitError,
+    commands::{CommandError, CommandResult},
+    db::{Db, GitCommandLog, GitCommandOutcome, GitCommandType},
+};
+
+#[derive(Deserialize, Debug, Clone, Copy, Type)]
+pub enum GitCommandLogExportFormat {
+    Json,
+    /// Commands in the order they were run, with their results as comments
+    ShellScript,
+}
+
+/// Write the open repository's full command log to `path`
+#[tauri::command]
+#[specta::specta]
+pub async fn export_git_command_log(
+    db: tauri::State<'_, Db>,
+    format: GitCommandLogExportFormat,
+    path: String,
+) -> CommandResult<()> {
+    let repository_id = db
+        .state
+        .lock()
+        .await
+        .open_repository
+        .as_ref()
+        .map(|repo| repo.id)
+        .ok_or(GitError::NoRepoOpen)?;
+    let log = db.get_full_git_command_log(repository_id).await?;
+
+    let contents = match format {
+        GitCommandLogExportFormat::Json => serde_json::to_string_pretty(&log)
+            .map_err(|err| CommandError::Other(err.to_string()))?,
+        GitCommandLogExportFormat::ShellScript => shell_script(&log),
+    };
+
+    tokio::fs::write(&path, contents)
+        .await
+        .map_err(|err| CommandError::Other(format!("Failed to write {path}: {err}")))
+}
+
+fn shell_script(log: &[GitCommandLog]) -> String {
+    let mut script = String::from(
+        "#!/bin/sh\n# Git commands exported from the command log.\n# Commands logged before their arguments were recorded are commented out.\n",
+    );
+    let mut working_directory = None;
+
+    for entry in log {
+        if entry.working_directory.is_some() && entry.working_directory != working_directory {
+            working_directory = entry.working_directory.clone();
+            script.push_str(&format!(
+                "\ncd {}\n",
+                shell_quote(working_directory.as_deref().unwrap_or_default())
+            ));
+        }
+
+        let command_type = match entry.command_type {
+            GitCommandType::Query => "query",
+            GitCommandType::Mutation => "mutation",
+        };
+        let outcome = match (entry.outcome, entry.exit_code) {
+            (GitCommandOutcome::Success, _) => "succeeded".into(),
+            (GitCommandOutcome::Cancelled, _) => "cancelled".into(),
+            (GitCommandOutcome::TimedOut, _) => "timed out".into(),
+            (GitCommandOutcome::Failed, Some(code)) => format!("exited with {code}"),
+            (GitCommandOutcome::Failed, None) => "failed".into(),
+        };
+        script.push_str(&format!(
+            "# {} {command_type}, {outcome} after {}ms\n",
+            entry.created_at, entry.duration_ms
+        ));
+        match &entry.args.0 {
+            Some(args) => {
+                let args: Vec<String> = args.iter().map(|arg| shell_quote(arg)).collect();
+                script.push_str(&format!("git {}\n", args.join(" ")));
+            }
+            None => script.push_str(&format!("# git {}\n", entry.command.replace('\n', " "))),
+        }
+    }
+
+    script
+}
+
+/// Quote a string in single quotes for a POSIX shell
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::db::{GitCommandArgs, GitCommandLog, GitCommandOutcome, GitCommandType};
+
+    use super::{shell_quote, shell_script};
+
+    fn entry(command: &str, args: Option<&[&str]>) -> GitCommandLog {
+        GitCommandLog {
+            id: 1,
+            command: command.into(),
+            args: GitCommandArgs(args.map(|args| args.iter().map(|arg| arg.to_string()).collect())),
+            command_type: GitCommandType::Mutation,
+            created_at: "2026-10-19T12:00:00".parse().unwrap(),
+            progress: None,
+            outcome: GitCommandOutcome::Success,
+            exit_code: Some(0),
+            duration_ms: 12,
+            working_directory: Some("/repo".into()),
+            stdout: None,
+            stderr: None,
+        }
+    }
+
+    #[test]
+    fn quotes_for_shell() {
+        assert_eq!(shell_quote("plain"), "'plain'");
+        assert_eq!(shell_quote("with space"), "'with space'");
+        assert_eq!(shell_quote("$(rm -rf ~); echo"), "'$(rm -rf ~); echo'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        assert_eq!(shell_quote(""), "''");
+    }
+
+    #[test]
+    fn exports_quoted_arguments() {
+        let script = shell_script(&[
+            entry(
+                "commit -m fix $(whoami)",
+                Some(&["commit", "-m", "fix $(whoami)"]),
+            ),
+            entry("log --format=%H\x01%s", None),
+        ]);
+
+        assert!(script.contains("\ncd '/repo'\n"));
+        assert!(script.contains("\ngit 'commit' '-m' 'fix $(whoami)'\n"));
+        assert!(script.contains("\n# git log --format=%H\x01%s\n"));
+    }
+}
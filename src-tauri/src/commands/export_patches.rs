@@ -0,0 +1,28 @@
+use crate::{cli::GitCommand, db::GitCommandType, structures::patch::PatchRevisions};
+
+use super::CommandResult;
+
+/// Write a patch file for each commit into `directory`, returning the paths of the created files
+#[tauri::command]
+#[specta::specta]
+pub async fn export_patches(
+    app_handle: tauri::AppHandle,
+    revisions: PatchRevisions,
+    directory: String,
+) -> CommandResult<Vec<String>> {
+    let mut command = GitCommand::new("format-patch");
+    command.arg("--output-directory").arg(directory);
+    match revisions {
+        PatchRevisions::Commit(hash) => command.arg("-1").arg(hash.0),
+        PatchRevisions::Range { base, head } => command
+            .arg("--end-of-options") // Don't treat revisions starting with `-` as options
+            .arg(format!("{base}..{head}")),
+    };
+
+    Ok(command
+        .run(&app_handle, GitCommandType::Query)
+        .await?
+        .lines()
+        .map(|path| path.into())
+        .collect())
+}
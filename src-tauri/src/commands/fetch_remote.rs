@@ -0,0 +1,19 @@
+use crate::{cli::GitCommand, db::GitCommandType};
+
+use super::CommandResult;
+
+/// Fetch a single remote, unlike `git_fetch` which fetches all of them
+#[tauri::command]
+#[specta::specta]
+pub async fn fetch_remote(app_handle: tauri::AppHandle, name: String) -> CommandResult<()> {
+    GitCommand::new("fetch")
+        .progress()
+        // Lists the updated refs
+        .log_output()
+        .arg("--end-of-options") // Don't treat a remote starting with `-` as an option
+        .arg(name)
+        .run(&app_handle, GitCommandType::Mutation)
+        .await?;
+
+    Ok(())
+}
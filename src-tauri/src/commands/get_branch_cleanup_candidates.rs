@@ -0,0 +1,60 @@
+use std::collections::HashSet;
+
+use crate::{
+    cli::GitCommand,
+    db::{Db, GitCommandType},
+    structures::branch_cleanup::{BranchCleanupCandidate, CleanupBranch, CLEANUP_BRANCH_FIELDS},
+};
+
+use super::{CommandError, CommandResult};
+
+/// List every local branch with the details needed to decide whether it can be cleaned up
+pub async fn cleanup_branches(app_handle: &tauri::AppHandle) -> CommandResult<Vec<CleanupBranch>> {
+    let format = GitCommand::create_format_arg(CLEANUP_BRANCH_FIELDS, "%00");
+    GitCommand::new("for-each-ref")
+        .arg(format!("--format={format}"))
+        .arg("refs/heads")
+        .run(app_handle, GitCommandType::Query)
+        .await?
+        .lines()
+        .map(|line| line.parse().map_err(CommandError::Parse))
+        .collect()
+}
+
+/// List the names of the local branches merged into `base`, one per line
+pub async fn merged_branches(app_handle: &tauri::AppHandle, base: &str) -> CommandResult<String> {
+    if base.trim().is_empty() {
+        return Err(CommandError::Other(
+            "Choose a base branch to compare against".into(),
+        ));
+    }
+
+    Ok(GitCommand::new("for-each-ref")
+        .arg("--format=%(refname:lstrip=2)")
+        .arg(format!("--merged={base}"))
+        .arg("refs/heads")
+        .run(app_handle, GitCommandType::Query)
+        .await?)
+}
+
+/// Preview the local branches merged into `base` or whose upstream is gone.
+/// Branches that are checked out or match a protected name are never included.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_branch_cleanup_candidates(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    base: String,
+) -> CommandResult<Vec<BranchCleanupCandidate>> {
+    let protected_branches = db.get_settings().await.protected_branches;
+    let branches = cleanup_branches(&app_handle).await?;
+    let merged = merged_branches(&app_handle, &base).await?;
+    let merged: HashSet<&str> = merged.lines().collect();
+
+    Ok(BranchCleanupCandidate::from_branches(
+        branches,
+        &merged,
+        &base,
+        &protected_branches,
+    ))
+}
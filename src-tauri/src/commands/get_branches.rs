@@ -15,6 +15,7 @@ const LOCAL_BRANCH_FIELDS: &[&str] = &[
     "(upstream:short)",
     "(upstream:track,nobracket)",
     "(objectname)",
+    "(worktreepath)",
 ];
 const REMOTE_BRANCH_FIELDS: &[&str] = &["(refname:short)", "(objectname)"];
 
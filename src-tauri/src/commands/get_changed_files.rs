@@ -1,7 +1,13 @@
 use crate::{
     cli::GitCommand,
     db::GitCommandType,
-    structures::{file::File, file_status::FileStatus, hash::GitHash},
+    structures::{
+        file::File,
+        file_stats::FileStats,
+        file_status::FileStatus,
+        hash::GitHash,
+        submodule::{Submodule, SubmoduleChange},
+    },
 };
 
 use super::{CommandError, CommandResult};
@@ -14,6 +20,8 @@ pub async fn get_changed_files(
     let items = GitCommand::new("status")
         .arg("-z") // Separate commits with NULs for parsing
         .arg("--porcelain=2") // Use stable parsable porcelain format
+        // Refreshing the index would change it, so the watcher would run this again
+        .env("GIT_OPTIONAL_LOCKS", "0")
         .run(&app_handle, GitCommandType::Query)
         .await?;
 
@@ -39,7 +47,14 @@ pub async fn get_changed_files(
                     })
                     .collect();
 
-                let mut parts = item.split_ascii_whitespace().skip(4);
+                let mut parts = item.split_ascii_whitespace();
+                let submodule = SubmoduleChange::from_status_field(
+                    parts
+                        .next()
+                        .ok_or(CommandError::Parse("Failed to get submodule state".into()))?,
+                )
+                .map_err(CommandError::Parse)?;
+                let mut parts = parts.skip(3);
                 if line_type == "u" {
                     parts.next();
                 }
@@ -82,33 +97,51 @@ pub async fn get_changed_files(
                     _ => unreachable!(),
                 };
 
+                let head_hash = GitHash::from_optional(head_hash)
+                    .map_err(|_err| CommandError::Parse("Failed to parse head hash".into()))?;
+                let index_hash = GitHash::from_optional(index_hash)
+                    .map_err(|_err| CommandError::Parse("Failed to parse index hash".into()))?;
+
                 if let Some(staged_status) = &status[0] {
                     staged_files.push(File {
-                        src_hash: GitHash::from_optional(head_hash).map_err(|_err| {
-                            CommandError::Parse("Failed to parse head hash".into())
-                        })?,
-                        dst_hash: GitHash::from_optional(index_hash).map_err(|_err| {
-                            CommandError::Parse("Failed to parse index hash".into())
-                        })?,
+                        // The flags describe the work tree, only the recorded commit is staged
+                        submodule: submodule.map(|_| SubmoduleChange {
+                            commit_changed: head_hash != index_hash,
+                            ..Default::default()
+                        }),
+                        src_hash: head_hash.clone(),
+                        dst_hash: index_hash.clone(),
                         status: *staged_status,
                         score,
                         src_path: path.into(),
                         dst_path: dst_path.map(|s| s.into()),
+                        stats: None,
                     });
                 }
 
                 if let Some(unstaged_status) = &status[1] {
-                    unstaged_files.push(File {
-                        src_hash: GitHash::from_optional(head_hash).map_err(|_err| {
-                            CommandError::Parse("Failed to parse head hash".into())
-                        })?,
-                        dst_hash: GitHash::from_optional(index_hash).map_err(|_err| {
-                            CommandError::Parse("Failed to parse index hash".into())
-                        })?,
-                        status: *unstaged_status,
-                        score,
-                        src_path: path.into(),
-                        dst_path: dst_path.map(|s| s.into()),
+                    unstaged_files.push(match submodule {
+                        // The checked out commit is filled in below, as status doesn't show it
+                        Some(submodule) => File {
+                            src_hash: index_hash,
+                            dst_hash: None,
+                            status: *unstaged_status,
+                            score,
+                            src_path: path.into(),
+                            dst_path: dst_path.map(|s| s.into()),
+                            stats: None,
+                            submodule: Some(submodule),
+                        },
+                        None => File {
+                            src_hash: head_hash,
+                            dst_hash: index_hash,
+                            status: *unstaged_status,
+                            score,
+                            src_path: path.into(),
+                            dst_path: dst_path.map(|s| s.into()),
+                            stats: None,
+                            submodule: None,
+                        },
                     });
                 }
             }
@@ -120,11 +153,68 @@ pub async fn get_changed_files(
                 score: None,
                 src_path: item.into(),
                 dst_path: None,
+                stats: None,
+                submodule: None,
             }),
             // Ignore unknown line types
             _ => {}
         }
     }
 
-    Ok((unstaged_files, staged_files))
+    let submodule_paths: Vec<String> = unstaged_files
+        .iter()
+        .filter(|file| file.submodule.is_some())
+        .map(|file| file.src_path.clone())
+        .collect();
+    if !submodule_paths.is_empty() {
+        let mut command = GitCommand::new("submodule");
+        command.arg("status").arg("--");
+        for path in &submodule_paths {
+            command.arg(path);
+        }
+        // Fails for submodules missing from .gitmodules, which are left without a checked out commit
+        if let Ok(status) = command.run(&app_handle, GitCommandType::Query).await {
+            let submodules = Submodule::with_checked_out_commits(
+                submodule_paths
+                    .iter()
+                    .map(|path| Submodule::new(path, path))
+                    .collect(),
+                &status,
+            )
+            .map_err(CommandError::Parse)?;
+            for file in unstaged_files
+                .iter_mut()
+                .filter(|file| file.submodule.is_some())
+            {
+                // None if the submodule isn't checked out, e.g. it was deleted
+                file.dst_hash = submodules
+                    .iter()
+                    .find(|submodule| submodule.path == file.src_path)
+                    .and_then(|submodule| submodule.checked_out_commit.clone());
+            }
+        }
+    }
+
+    let unstaged_stats = GitCommand::new("diff")
+        .arg("--numstat") // Count inserted and deleted lines
+        .arg("-z") // Separate files with NULs for parsing
+        .run(&app_handle, GitCommandType::Query)
+        .await?;
+    let staged_stats = GitCommand::new("diff")
+        .arg("--cached") // Compare the index to HEAD
+        .arg("--numstat")
+        .arg("-z")
+        .run(&app_handle, GitCommandType::Query)
+        .await?;
+
+    Ok((
+        File::with_stats(
+            unstaged_files,
+            &FileStats::from_numstat(&unstaged_stats).map_err(CommandError::Parse)?,
+        ),
+        File::with_stats(
+            staged_files,
+            &FileStats::from_numstat(&staged_stats).map_err(CommandError::Parse)?,
+        ),
+    ))
 }
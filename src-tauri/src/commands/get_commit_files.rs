@@ -1,7 +1,12 @@
 use crate::{
     cli::GitCommand,
     db::GitCommandType,
-    structures::{file::File, hash::GitHash},
+    structures::{
+        file::{CommitFiles, File},
+        file_diff::MergeDiffMode,
+        file_stats::{DiffStats, FileStats},
+        hash::GitHash,
+    },
 };
 
 use super::{CommandError, CommandResult};
@@ -11,25 +16,60 @@ use super::{CommandError, CommandResult};
 pub async fn get_commit_files(
     app_handle: tauri::AppHandle,
     commit_hash: GitHash,
-) -> CommandResult<Vec<File>> {
-    let files = GitCommand::new("show")
-        .arg(commit_hash.0)
+    merge_diff_mode: MergeDiffMode,
+) -> CommandResult<CommitFiles> {
+    let files = diff_command(&commit_hash, merge_diff_mode)
         .arg("--raw") // Show a summary of changes without the full diffs
         .arg("--abbrev=40") // Show full git hashes
         .arg("-z") // Separate commits with NULs for parsing
-        .arg("--diff-merges=1") // Show merge commits in the same format as regular commits
-        .arg("--format=") // Disable the commit info so we only get the files
         .run(&app_handle, GitCommandType::Query)
         .await?;
+    let files = File::from_raw(&files).map_err(CommandError::Parse)?;
 
-    let files = files
-        .strip_prefix(':')
-        .ok_or(CommandError::Parse("Failed to strip files prefix".into()))?
-        .strip_suffix('\x00')
-        .ok_or(CommandError::Parse("Failed to strip files suffix".into()))?
-        .split("\x00:");
+    // Git only counts lines against the first parent for combined diffs, which would be misleading
+    if let MergeDiffMode::Combined | MergeDiffMode::DenseCombined = merge_diff_mode {
+        return Ok(CommitFiles { files, stats: None });
+    }
 
-    files
-        .map(|line| line.parse().map_err(CommandError::Parse))
-        .collect()
+    let stats = diff_command(&commit_hash, merge_diff_mode)
+        .arg("--numstat") // Count inserted and deleted lines
+        .arg("-z")
+        .run(&app_handle, GitCommandType::Query)
+        .await?;
+    let files = File::with_stats(
+        files,
+        &FileStats::from_numstat(&stats).map_err(CommandError::Parse)?,
+    );
+
+    Ok(CommitFiles {
+        stats: Some(DiffStats::from_files(&files)),
+        files,
+    })
+}
+
+/// Create a command that shows the changes in a commit, without choosing the output format
+fn diff_command(commit_hash: &GitHash, merge_diff_mode: MergeDiffMode) -> GitCommand {
+    match merge_diff_mode {
+        // There's no `--diff-merges` option for other parents, so diff against the parent directly
+        MergeDiffMode::Parent(parent) if parent > 1 => {
+            let mut command = GitCommand::new("diff");
+            command
+                .arg(format!("{}^{parent}", commit_hash.0))
+                .arg(&commit_hash.0);
+            command
+        }
+        mode => {
+            let mut command = GitCommand::new("show");
+            command
+                .arg(&commit_hash.0)
+                .arg(match mode {
+                    // Show merge commits in the same format as regular commits
+                    MergeDiffMode::Parent(_) => "--diff-merges=1",
+                    MergeDiffMode::Combined => "--diff-merges=c",
+                    MergeDiffMode::DenseCombined => "--diff-merges=cc",
+                })
+                .arg("--format="); // Disable the commit info so we only get the files
+            command
+        }
+    }
 }
@@ -1,22 +1,88 @@
+use std::sync::{Arc, Mutex};
+
+use base64::Engine;
+use tauri::Manager;
+
 use crate::{
     cli::GitCommand,
-    db::GitCommandType,
-    structures::{file_diff::FileDiff, file_diff_meta::FileDiffMeta, hash::GitHash},
+    db::{Db, GitCommandType},
+    structures::{
+        file_diff::{BinaryFile, DiffLayout, DiffRevisions, FileDiff, ImageFile, MergeDiffMode},
+        file_diff_meta::{CombinedFileDiffMeta, FileDiffMeta},
+        git_version::CHECK_ATTR_SOURCE_VERSION,
+        hash::GitHash,
+        moved_block::{changed_lines, detect_moved_blocks, MovedBlock},
+        submodule::SUBMODULE_MODE,
+    },
+    utils::{
+        encoding::decode_file,
+        mime::{is_displayable_image, mime_type_from_path},
+    },
 };
 
 use super::{CommandError, CommandResult};
 
+/// Moved blocks of the last diffed revisions, as detecting them diffs every file and the files of
+/// a commit are usually viewed one after another. Revisions are commits, so they never go stale.
+#[derive(Default)]
+pub struct MovedBlockCache(Mutex<Option<CachedMovedBlocks>>);
+
+/// Source and destination revisions, and the blocks moved between them
+type CachedMovedBlocks = ((String, String), Arc<Vec<MovedBlock>>);
+
 #[tauri::command]
 #[specta::specta]
 pub async fn get_file_diff(
     app_handle: tauri::AppHandle,
-    commit_hash: GitHash,
+    db: tauri::State<'_, Db>,
+    moved_block_cache: tauri::State<'_, MovedBlockCache>,
+    revisions: DiffRevisions,
     path: String,
+    layout: DiffLayout,
 ) -> CommandResult<FileDiff> {
+    let settings = db.get_settings().await;
+    let max_highlight_size = settings.max_highlight_size as usize;
+
+    let (src_revision, dst_revision) = match revisions {
+        DiffRevisions::Commit {
+            hash,
+            merge_diff_mode,
+        } => {
+            let parent = match merge_diff_mode {
+                MergeDiffMode::Parent(parent) => {
+                    // `hash^0` is the commit itself, which would show no changes
+                    if parent == 0 || parent > parent_count(&app_handle, &hash).await? {
+                        return Err(CommandError::Other(format!(
+                            "Commit {} has no parent {parent}",
+                            hash.0
+                        )));
+                    }
+                    parent
+                }
+                MergeDiffMode::Combined | MergeDiffMode::DenseCombined => {
+                    if let Some(diff) = combined_diff(
+                        &app_handle,
+                        &hash,
+                        &path,
+                        merge_diff_mode,
+                        max_highlight_size,
+                    )
+                    .await?
+                    {
+                        return Ok(diff);
+                    }
+                    1
+                }
+            };
+            (format!("{}^{parent}", hash.0), hash.0)
+        }
+        DiffRevisions::Range { src_hash, dst_hash } => (src_hash.0, dst_hash.0),
+    };
+
     let diff_meta: FileDiffMeta = GitCommand::new("diff")
         .arg("--abbrev=40")
-        .arg(format!("{}^", commit_hash.0))
-        .arg(commit_hash.0)
+        .arg(&src_revision)
+        .arg(&dst_revision)
         .arg("--")
         .arg(path)
         .run(&app_handle, GitCommandType::Query)
@@ -24,26 +90,282 @@ pub async fn get_file_diff(
         .parse()
         .map_err(CommandError::Parse)?;
 
-    let src_file = match &diff_meta.src_hash {
-        Some(hash) => Some(
-            GitCommand::new("cat-file")
-                .arg("blob")
-                .arg(hash.0.clone())
-                .run(&app_handle, GitCommandType::Query)
-                .await?,
-        ),
+    // Submodules are stored as commits, which have no contents to diff
+    if diff_meta.submodule {
+        return Ok(FileDiff::Submodule {
+            src: diff_meta.src_hash,
+            dst: diff_meta.dst_hash,
+        });
+    }
+
+    if !diff_meta.binary {
+        let src_file = match &diff_meta.src_hash {
+            Some(hash) => Some(decode_file(
+                &blob(&app_handle, hash).await?,
+                encoding_attribute(&app_handle, &src_revision, &diff_meta.src_path)
+                    .await?
+                    .as_deref(),
+            )),
+            None => None,
+        };
+        let dst_file = match &diff_meta.dst_hash {
+            Some(hash) => Some(decode_file(
+                &blob(&app_handle, hash).await?,
+                encoding_attribute(&app_handle, &dst_revision, &diff_meta.dst_path)
+                    .await?
+                    .as_deref(),
+            )),
+            None => None,
+        };
+
+        let (src_path, dst_path) = (diff_meta.src_path.clone(), diff_meta.dst_path.clone());
+        let mut diff = FileDiff::from(diff_meta, src_file, dst_file, max_highlight_size)
+            .map_err(CommandError::Parse)?;
+        diff.mark_moved_lines(
+            &moved_blocks(
+                &app_handle,
+                &moved_block_cache,
+                &src_revision,
+                &dst_revision,
+            )
+            .await?,
+            &src_path,
+            &dst_path,
+        );
+
+        return Ok(match layout {
+            DiffLayout::Unified => diff,
+            DiffLayout::Split => diff.into_split(),
+        });
+    }
+
+    let src_mime_type = mime_type_from_path(&diff_meta.src_path);
+    let dst_mime_type = mime_type_from_path(&diff_meta.dst_path);
+
+    let src_size = match &diff_meta.src_hash {
+        Some(hash) => Some(blob_size(&app_handle, hash).await?),
         None => None,
     };
+    let dst_size = match &diff_meta.dst_hash {
+        Some(hash) => Some(blob_size(&app_handle, hash).await?),
+        None => None,
+    };
+
+    let is_image = is_displayable_image(src_mime_type)
+        && is_displayable_image(dst_mime_type)
+        && [src_size, dst_size]
+            .into_iter()
+            .flatten()
+            .all(|size| size <= settings.max_image_size as usize);
+
+    if is_image {
+        let src = match &diff_meta.src_hash {
+            Some(hash) => Some(ImageFile {
+                size: src_size.unwrap_or_default(),
+                mime_type: src_mime_type.into(),
+                base64: base64::engine::general_purpose::STANDARD
+                    .encode(blob(&app_handle, hash).await?),
+            }),
+            None => None,
+        };
+        let dst = match &diff_meta.dst_hash {
+            Some(hash) => Some(ImageFile {
+                size: dst_size.unwrap_or_default(),
+                mime_type: dst_mime_type.into(),
+                base64: base64::engine::general_purpose::STANDARD
+                    .encode(blob(&app_handle, hash).await?),
+            }),
+            None => None,
+        };
+        return Ok(FileDiff::Image { src, dst });
+    }
+
+    Ok(FileDiff::Binary {
+        src: src_size.map(|size| BinaryFile {
+            size,
+            mime_type: src_mime_type.into(),
+        }),
+        dst: dst_size.map(|size| BinaryFile {
+            size,
+            mime_type: dst_mime_type.into(),
+        }),
+    })
+}
+
+/// Get the combined diff of a file in a merge commit.
+/// Returns None if the commit isn't a merge or the file is binary or a submodule, so it can be diffed against the first parent instead.
+async fn combined_diff(
+    app_handle: &tauri::AppHandle,
+    commit_hash: &GitHash,
+    path: &str,
+    merge_diff_mode: MergeDiffMode,
+    max_highlight_size: usize,
+) -> CommandResult<Option<FileDiff>> {
+    // Combined diffs don't show the mode of a submodule unless it changed
+    let tree_entry = GitCommand::new("ls-tree")
+        .arg(&commit_hash.0)
+        .arg("--")
+        .arg(path)
+        .run(app_handle, GitCommandType::Query)
+        .await?;
+    if tree_entry.starts_with(&format!("{SUBMODULE_MODE} ")) {
+        return Ok(None);
+    }
+
+    let diff = GitCommand::new("show")
+        .arg(&commit_hash.0)
+        .arg(match merge_diff_mode {
+            MergeDiffMode::DenseCombined => "--diff-merges=cc",
+            _ => "--diff-merges=c",
+        })
+        .arg("--format=")
+        .arg("--full-index") // Combined diffs ignore `--abbrev`
+        .arg("--")
+        .arg(path)
+        .run(app_handle, GitCommandType::Query)
+        .await?;
+
+    // The file matches one of the parents, so there's nothing to show
+    if diff.is_empty() {
+        return Ok(Some(FileDiff::Combined(Vec::new())));
+    }
+    // Regular commits are shown with a regular diff
+    if diff.starts_with("diff --git ") {
+        return Ok(None);
+    }
+
+    let diff_meta: CombinedFileDiffMeta = diff.parse().map_err(CommandError::Parse)?;
+    if diff_meta.binary {
+        return Ok(None);
+    }
+
+    let mut src_files = Vec::new();
+    for (i, hash) in diff_meta.src_hashes.iter().enumerate() {
+        let parent = format!("{}^{}", commit_hash.0, i + 1);
+        src_files.push(match hash {
+            Some(hash) => Some(decode_file(
+                &blob(app_handle, hash).await?,
+                encoding_attribute(app_handle, &parent, &diff_meta.path)
+                    .await?
+                    .as_deref(),
+            )),
+            None => None,
+        });
+    }
     let dst_file = match &diff_meta.dst_hash {
-        Some(hash) => Some(
-            GitCommand::new("cat-file")
-                .arg("blob")
-                .arg(hash.0.clone())
-                .run(&app_handle, GitCommandType::Query)
-                .await?,
-        ),
+        Some(hash) => Some(decode_file(
+            &blob(app_handle, hash).await?,
+            encoding_attribute(app_handle, &commit_hash.0, &diff_meta.path)
+                .await?
+                .as_deref(),
+        )),
         None => None,
     };
 
-    FileDiff::from(diff_meta, src_file, dst_file).map_err(CommandError::Parse)
+    Ok(Some(FileDiff::from_combined(
+        diff_meta,
+        src_files,
+        dst_file,
+        max_highlight_size,
+    )))
+}
+
+/// Find blocks of lines that were moved within or between files in a diff
+async fn moved_blocks(
+    app_handle: &tauri::AppHandle,
+    cache: &MovedBlockCache,
+    src_revision: &str,
+    dst_revision: &str,
+) -> CommandResult<Arc<Vec<MovedBlock>>> {
+    let revisions = (src_revision.to_string(), dst_revision.to_string());
+    if let Some((cached_revisions, blocks)) = cache.0.lock().unwrap().as_ref() {
+        if *cached_revisions == revisions {
+            return Ok(blocks.clone());
+        }
+    }
+
+    let diff = GitCommand::new("diff")
+        .arg("--abbrev=40")
+        .arg("-U0") // Only changed lines are needed
+        .arg(src_revision)
+        .arg(dst_revision)
+        .run(app_handle, GitCommandType::Query)
+        .await?;
+
+    let (removed, added) = changed_lines(&diff);
+    let blocks = Arc::new(detect_moved_blocks(&removed, &added));
+    *cache.0.lock().unwrap() = Some((revisions, blocks.clone()));
+
+    Ok(blocks)
+}
+
+/// Get the number of parents of a commit
+async fn parent_count(app_handle: &tauri::AppHandle, hash: &GitHash) -> CommandResult<usize> {
+    // Outputs the commit's hash followed by its parents' hashes
+    let hashes = GitCommand::new("rev-list")
+        .arg("--parents")
+        .arg("-n1")
+        .arg(&hash.0)
+        .run(app_handle, GitCommandType::Query)
+        .await?;
+
+    Ok(hashes.split_whitespace().count().saturating_sub(1))
+}
+
+/// Get the raw contents of a blob
+async fn blob(app_handle: &tauri::AppHandle, hash: &GitHash) -> CommandResult<Vec<u8>> {
+    Ok(GitCommand::new("cat-file")
+        .arg("blob")
+        .arg(hash.0.clone())
+        .run_bytes(app_handle, GitCommandType::Query)
+        .await?)
+}
+
+/// Get the size of a blob in bytes without reading it
+async fn blob_size(app_handle: &tauri::AppHandle, hash: &GitHash) -> CommandResult<usize> {
+    GitCommand::new("cat-file")
+        .arg("-s")
+        .arg(hash.0.clone())
+        .run(app_handle, GitCommandType::Query)
+        .await?
+        .trim()
+        .parse()
+        .map_err(|err| CommandError::Parse(format!("Failed to parse blob size: {err}")))
+}
+
+/// Get the encoding of a file at `revision` from the `encoding` or `working-tree-encoding` git
+/// attributes. Git before 2.40 can only read the working tree's attributes, which may differ.
+async fn encoding_attribute(
+    app_handle: &tauri::AppHandle,
+    revision: &str,
+    path: &str,
+) -> CommandResult<Option<String>> {
+    let can_read_revision = app_handle
+        .state::<Db>()
+        .state
+        .lock()
+        .await
+        .git_version
+        .as_ref()
+        .and_then(|check| check.version)
+        .is_some_and(|version| version >= CHECK_ATTR_SOURCE_VERSION);
+
+    let attributes = GitCommand::new("check-attr")
+        .arg("-z")
+        .arg_if(&format!("--source={revision}"), can_read_revision)
+        .arg("encoding")
+        .arg("working-tree-encoding")
+        .arg("--")
+        .arg(path)
+        .run(app_handle, GitCommandType::Query)
+        .await?;
+
+    // Output is in the format `<path> NUL <attribute> NUL <value> NUL`
+    Ok(attributes
+        .split('\x00')
+        .collect::<Vec<_>>()
+        .chunks_exact(3)
+        .map(|attribute| attribute[2])
+        .find(|value| !matches!(*value, "unspecified" | "unset" | "set"))
+        .map(|value| value.into()))
 }
@@ -1,13 +1,19 @@
 use crate::{
     commands::CommandResult,
-    db::{Db, GitCommandLog, GitCommandType},
+    db::{Db, GitCommandLogCursor, GitCommandLogPage, GitCommandType},
 };
 
+/// Get a page of the command log, newest first. Pass the previous page's `next_cursor` to continue.
 #[tauri::command]
 #[specta::specta]
 pub async fn get_git_command_log(
     db: tauri::State<'_, Db>,
     filter: Option<GitCommandType>,
-) -> CommandResult<Vec<GitCommandLog>> {
-    Ok(db.get_git_command_log(filter).await?)
+    search: Option<String>,
+    cursor: Option<GitCommandLogCursor>,
+    limit: u32,
+) -> CommandResult<GitCommandLogPage> {
+    Ok(db
+        .get_git_command_log(filter, search, cursor, limit.into())
+        .await?)
 }
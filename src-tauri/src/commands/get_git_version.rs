@@ -0,0 +1,12 @@
+use crate::{cli::GitVersionCheck, commands::CommandResult, db::Db};
+
+/// Get the result of checking the git executable, which is done at startup and when settings change
+#[tauri::command]
+#[specta::specta]
+pub async fn get_git_version(db: tauri::State<'_, Db>) -> CommandResult<GitVersionCheck> {
+    let check = db.state.lock().await.git_version.clone();
+    Ok(match check {
+        Some(check) => check,
+        None => db.check_git_version().await,
+    })
+}
@@ -0,0 +1,13 @@
+use crate::{
+    commands::CommandResult,
+    watcher::{RepositoryWatchFailedEvent, RepositoryWatcher},
+};
+
+/// Get why the open repository isn't being watched for changes, if it isn't
+#[tauri::command]
+#[specta::specta]
+pub async fn get_repository_watch_failure(
+    watcher: tauri::State<'_, RepositoryWatcher>,
+) -> CommandResult<Option<RepositoryWatchFailedEvent>> {
+    Ok(watcher.failure())
+}
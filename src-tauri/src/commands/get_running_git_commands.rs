@@ -0,0 +1,11 @@
+use crate::cli::{GitCommandRegistry, RunningGitCommand};
+
+use super::CommandResult;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_running_git_commands(
+    registry: tauri::State<'_, GitCommandRegistry>,
+) -> CommandResult<Vec<RunningGitCommand>> {
+    Ok(registry.running())
+}
@@ -0,0 +1,7 @@
+use crate::{commands::CommandResult, db::Db, settings::Settings};
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_settings(db: tauri::State<'_, Db>) -> CommandResult<Settings> {
+    Ok(db.get_settings().await)
+}
@@ -0,0 +1,45 @@
+use crate::{cli::GitCommand, db::GitCommandType, structures::submodule::Submodule};
+
+use super::{CommandError, CommandResult};
+
+/// List the submodules configured in `.gitmodules` with their recorded and checked out commits
+#[tauri::command]
+#[specta::specta]
+pub async fn get_submodules(app_handle: tauri::AppHandle) -> CommandResult<Vec<Submodule>> {
+    let index = GitCommand::new("ls-files")
+        .arg("--stage") // Show the mode and hash of each entry
+        .arg("-z")
+        .run(&app_handle, GitCommandType::Query)
+        .await?;
+    // Reading a missing `.gitmodules` is an error, so check it's tracked first
+    if !index
+        .split('\0')
+        .any(|entry| entry.ends_with("\t.gitmodules"))
+    {
+        return Ok(Vec::new());
+    }
+
+    let gitmodules = GitCommand::new("config")
+        .arg("-z")
+        .arg("--file")
+        .arg(".gitmodules")
+        .arg("--list")
+        .run(&app_handle, GitCommandType::Query)
+        .await?;
+    let submodule_status = GitCommand::new("submodule")
+        .arg("status")
+        .run(&app_handle, GitCommandType::Query)
+        .await?;
+    let status = GitCommand::new("status")
+        .arg("-z")
+        .arg("--porcelain=2")
+        .run(&app_handle, GitCommandType::Query)
+        .await?;
+
+    let submodules = Submodule::from_gitmodules(&gitmodules);
+    let submodules =
+        Submodule::with_recorded_commits(submodules, &index).map_err(CommandError::Parse)?;
+    let submodules = Submodule::with_checked_out_commits(submodules, &submodule_status)
+        .map_err(CommandError::Parse)?;
+    Submodule::with_changes(submodules, &status).map_err(CommandError::Parse)
+}
@@ -0,0 +1,15 @@
+use crate::{cli::GitCommand, db::GitCommandType, structures::worktree::Worktree};
+
+use super::{CommandError, CommandResult};
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_worktrees(app_handle: tauri::AppHandle) -> CommandResult<Vec<Worktree>> {
+    let output = GitCommand::new("worktree")
+        .arg("list")
+        .arg("--porcelain")
+        .run(&app_handle, GitCommandType::Query)
+        .await?;
+
+    Worktree::from_porcelain(&output).map_err(CommandError::Parse)
+}
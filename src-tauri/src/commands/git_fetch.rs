@@ -1,14 +1,33 @@
-use crate::{cli::GitCommand, db::GitCommandType};
+use crate::{
+    cli::{GitCommand, GitError},
+    db::{Db, GitCommandType},
+};
 
 use super::CommandResult;
 
 #[tauri::command]
 #[specta::specta]
-pub async fn git_fetch(app_handle: tauri::AppHandle) -> CommandResult<()> {
+pub async fn git_fetch(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+) -> CommandResult<()> {
     GitCommand::new("fetch")
         .arg("--all")
+        .progress()
+        // Lists the updated refs
+        .log_output()
         .run(&app_handle, GitCommandType::Mutation)
         .await?;
 
+    let repository_id = db
+        .state
+        .lock()
+        .await
+        .open_repository
+        .as_ref()
+        .map(|repo| repo.id)
+        .ok_or(GitError::NoRepoOpen)?;
+    db.set_last_fetched_at(repository_id).await?;
+
     Ok(())
 }
@@ -0,0 +1,99 @@
+use std::path::Path;
+
+use serde::Deserialize;
+use specta::Type;
+use tokio::fs;
+
+use crate::{
+    cli::GitCommand,
+    commands::{
+        add_repository_from_path::{register_and_open, resolve_repository_root},
+        CommandError, CommandResult,
+    },
+    db::{Db, GitCommandType, Repository},
+    utils::gitignore_template::GitignoreTemplate,
+    watcher::RepositoryWatcher,
+};
+
+#[derive(Debug, Deserialize, Type)]
+pub struct InitOptions {
+    /// e.g. `main`
+    pub initial_branch: String,
+    pub gitignore: Option<GitignoreTemplate>,
+    /// Create a README.md with the folder name as a heading
+    pub readme: bool,
+    /// Commit the starter files, or an empty commit if there aren't any
+    pub initial_commit: bool,
+}
+
+/// Create a new repository in `local_path`, creating the folder if needed, then add and open it
+#[tauri::command]
+#[specta::specta]
+pub async fn init_repository(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    watcher: tauri::State<'_, RepositoryWatcher>,
+    local_path: String,
+    options: InitOptions,
+) -> CommandResult<Repository> {
+    let initial_branch = options.initial_branch.trim();
+    if initial_branch.is_empty() {
+        return Err(CommandError::Other(
+            "Initial branch name can't be empty".into(),
+        ));
+    }
+
+    let path = Path::new(&local_path);
+    fs::create_dir_all(path)
+        .await
+        .map_err(|err| CommandError::Other(format!("Failed to create {local_path}: {err}")))?;
+
+    GitCommand::new("init")
+        .current_dir(&local_path)
+        .arg(format!("--initial-branch={initial_branch}"))
+        .run(&app_handle, GitCommandType::Mutation)
+        .await?;
+
+    let root = resolve_repository_root(&app_handle, path).await?;
+
+    // Existing files are staged too, e.g. when retrying after the initial commit failed
+    let mut starter_files = Vec::new();
+    if let Some(template) = options.gitignore {
+        write_new_file(&path.join(".gitignore"), template.contents()).await?;
+        starter_files.push(".gitignore");
+    }
+    if options.readme {
+        write_new_file(&path.join("README.md"), &format!("# {}\n", root.name)).await?;
+        starter_files.push("README.md");
+    }
+
+    // Commit before adding the repository, so a failed commit doesn't leave it half set up
+    if options.initial_commit {
+        if !starter_files.is_empty() {
+            let mut command = GitCommand::new("add");
+            command.current_dir(&root.local_path).arg("--");
+            for file in starter_files {
+                command.arg(file);
+            }
+            command.run(&app_handle, GitCommandType::Mutation).await?;
+        }
+        GitCommand::new("commit")
+            .current_dir(&root.local_path)
+            .arg("--allow-empty")
+            .arg("--message=Initial commit")
+            .run(&app_handle, GitCommandType::Mutation)
+            .await?;
+    }
+
+    register_and_open(&app_handle, &db, &watcher, path).await
+}
+
+/// Write a file unless it already exists, so the user's own files are kept
+async fn write_new_file(path: &Path, contents: &str) -> CommandResult<()> {
+    if fs::try_exists(path).await.unwrap_or(false) {
+        return Ok(());
+    }
+    fs::write(path, contents)
+        .await
+        .map_err(|err| CommandError::Other(format!("Failed to write {}: {err}", path.display())))
+}
@@ -0,0 +1,21 @@
+use crate::{cli::GitCommand, db::GitCommandType};
+
+use super::CommandResult;
+
+/// Copy the URLs of submodules from `.gitmodules` into the repository's config, all of them if
+/// `paths` is empty
+#[tauri::command]
+#[specta::specta]
+pub async fn init_submodules(
+    app_handle: tauri::AppHandle,
+    paths: Vec<String>,
+) -> CommandResult<()> {
+    let mut command = GitCommand::new("submodule");
+    command.arg("init").arg("--");
+    for path in paths {
+        command.arg(path);
+    }
+    command.run(&app_handle, GitCommandType::Mutation).await?;
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use crate::{cli::GitCommand, db::GitCommandType};
+
+use super::CommandResult;
+
+/// Prevent a linked worktree from being pruned, moved or removed, e.g. while it's on a removable drive
+#[tauri::command]
+#[specta::specta]
+pub async fn lock_worktree(
+    app_handle: tauri::AppHandle,
+    path: String,
+    reason: Option<String>,
+) -> CommandResult<()> {
+    let mut command = GitCommand::new("worktree");
+    command.arg("lock");
+    if let Some(reason) = reason.filter(|reason| !reason.trim().is_empty()) {
+        command.arg("--reason").arg(reason);
+    }
+    command
+        .arg("--")
+        .arg(path)
+        .run(&app_handle, GitCommandType::Mutation)
+        .await?;
+
+    Ok(())
+}
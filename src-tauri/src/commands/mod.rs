@@ -2,17 +2,58 @@ use thiserror::Error;
 
 use crate::cli::GitError;
 
+pub mod add_remote;
 pub mod add_repository_from_path;
+pub mod add_worktree;
+pub mod apply_patch;
+pub mod cancel_git_command;
+pub mod check_patch;
+pub mod clone_repository;
+pub mod compare_revisions;
+pub mod create_patch;
+pub mod deinit_submodules;
+pub mod delete_branches;
+pub mod export_git_command_log;
+pub mod export_patches;
+pub mod fetch_remote;
+pub mod get_branch_cleanup_candidates;
 pub mod get_branches;
 pub mod get_changed_files;
 pub mod get_commit_files;
 pub mod get_file_diff;
 pub mod get_git_command_log;
+pub mod get_git_version;
 pub mod get_graph;
 pub mod get_open_repository;
+pub mod get_remotes;
 pub mod get_repositories;
+pub mod get_repository_watch_failure;
+pub mod get_running_git_commands;
+pub mod get_settings;
+pub mod get_submodules;
+pub mod get_worktrees;
 pub mod git_fetch;
+pub mod init_repository;
+pub mod init_submodules;
+pub mod lock_worktree;
+pub mod prune_remote;
+pub mod prune_worktrees;
+pub mod register_worktree;
+pub mod relocate_repository;
+pub mod remove_remote;
+pub mod remove_repository;
+pub mod remove_worktree;
+pub mod rename_remote;
+pub mod rename_repository;
+pub mod reorder_repositories;
+pub mod set_git_command_log_retention;
 pub mod set_open_repository;
+pub mod set_remote_url;
+pub mod set_repository_pinned;
+pub mod sync_submodules;
+pub mod unlock_worktree;
+pub mod update_settings;
+pub mod update_submodules;
 
 #[derive(Error, Debug)]
 pub enum CommandError {
@@ -22,10 +63,26 @@ pub enum CommandError {
     Sqlx(#[from] sqlx::Error),
     #[error("failed to parse git output: {0}")]
     Parse(String),
+    #[error("{0} has already been added")]
+    DuplicateRepository(String),
+    #[error("Failed to watch repository: {0}")]
+    Watch(#[from] notify_debouncer_mini::notify::Error),
     #[error("{0}")]
     Other(String),
 }
 
+impl CommandError {
+    /// Convert a unique constraint error from adding or moving a repository into [`Self::DuplicateRepository`]
+    pub fn from_repository_path_error(err: sqlx::Error, local_path: &str) -> Self {
+        match err.as_database_error() {
+            Some(db_err) if db_err.is_unique_violation() => {
+                Self::DuplicateRepository(local_path.into())
+            }
+            _ => Self::Sqlx(err),
+        }
+    }
+}
+
 impl serde::Serialize for CommandError {
     fn serialize<S: serde::ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         serializer.serialize_str(self.to_string().as_ref())
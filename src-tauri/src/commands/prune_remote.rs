@@ -0,0 +1,17 @@
+use crate::{cli::GitCommand, db::GitCommandType};
+
+use super::CommandResult;
+
+/// Delete remote-tracking branches whose branch no longer exists on the remote
+#[tauri::command]
+#[specta::specta]
+pub async fn prune_remote(app_handle: tauri::AppHandle, name: String) -> CommandResult<()> {
+    GitCommand::new("remote")
+        .arg("prune")
+        .arg("--")
+        .arg(name)
+        .run(&app_handle, GitCommandType::Mutation)
+        .await?;
+
+    Ok(())
+}
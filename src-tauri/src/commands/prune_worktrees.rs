@@ -0,0 +1,15 @@
+use crate::{cli::GitCommand, db::GitCommandType};
+
+use super::CommandResult;
+
+/// Clean up the administrative files of worktrees whose folders were deleted
+#[tauri::command]
+#[specta::specta]
+pub async fn prune_worktrees(app_handle: tauri::AppHandle) -> CommandResult<()> {
+    GitCommand::new("worktree")
+        .arg("prune")
+        .run(&app_handle, GitCommandType::Mutation)
+        .await?;
+
+    Ok(())
+}
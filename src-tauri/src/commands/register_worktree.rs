@@ -0,0 +1,16 @@
+use std::path::Path;
+
+use crate::db::{Db, Repository};
+
+use super::{add_repository_from_path::register_repository, CommandResult};
+
+/// Add a worktree to the repository list without opening it, linked to its main repository
+#[tauri::command]
+#[specta::specta]
+pub async fn register_worktree(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    path: String,
+) -> CommandResult<Repository> {
+    register_repository(&app_handle, &db, Path::new(&path)).await
+}
@@ -0,0 +1,33 @@
+use std::path::Path;
+
+use crate::{
+    commands::{add_repository_from_path::resolve_repository_root, CommandError, CommandResult},
+    db::{Db, Repository},
+    watcher::RepositoryWatcher,
+};
+
+/// Update the path of a repository that was moved on disk
+#[tauri::command]
+#[specta::specta]
+pub async fn relocate_repository(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    watcher: tauri::State<'_, RepositoryWatcher>,
+    id: i64,
+    local_path: String,
+) -> CommandResult<Repository> {
+    let root = resolve_repository_root(&app_handle, Path::new(&local_path)).await?;
+
+    let repository = db
+        .relocate_repository(id, &root.local_path)
+        .await
+        .map_err(|err| CommandError::from_repository_path_error(err, &root.local_path))?;
+
+    // Watch the new path if it's open
+    let open_repository = db.state.lock().await.open_repository.clone();
+    if open_repository.as_ref().is_some_and(|repo| repo.id == id) {
+        watcher.watch(&app_handle, open_repository.as_ref())?;
+    }
+
+    Ok(repository)
+}
@@ -0,0 +1,17 @@
+use crate::{cli::GitCommand, db::GitCommandType};
+
+use super::CommandResult;
+
+/// Remove a remote and its remote-tracking branches
+#[tauri::command]
+#[specta::specta]
+pub async fn remove_remote(app_handle: tauri::AppHandle, name: String) -> CommandResult<()> {
+    GitCommand::new("remote")
+        .arg("remove")
+        .arg("--")
+        .arg(name)
+        .run(&app_handle, GitCommandType::Mutation)
+        .await?;
+
+    Ok(())
+}
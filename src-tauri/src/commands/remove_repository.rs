@@ -0,0 +1,19 @@
+use crate::{commands::CommandResult, db::Db, watcher::RepositoryWatcher};
+
+/// Forget a repository and its command log. The files on disk aren't touched.
+#[tauri::command]
+#[specta::specta]
+pub async fn remove_repository(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    watcher: tauri::State<'_, RepositoryWatcher>,
+    id: i64,
+) -> CommandResult<()> {
+    db.remove_repository(id).await?;
+
+    // Stop watching it if it was open
+    let open_repository = db.state.lock().await.open_repository.clone();
+    watcher.watch(&app_handle, open_repository.as_ref())?;
+
+    Ok(())
+}
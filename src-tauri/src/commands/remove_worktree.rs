@@ -0,0 +1,33 @@
+use crate::{
+    cli::GitCommand,
+    db::{Db, GitCommandType},
+};
+
+use super::CommandResult;
+
+/// Delete a linked worktree's folder, and remove it from the repository list if it was added.
+/// Without `force`, fails if the worktree has uncommitted changes or is locked.
+#[tauri::command]
+#[specta::specta]
+pub async fn remove_worktree(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    path: String,
+    force: bool,
+) -> CommandResult<()> {
+    let mut command = GitCommand::new("worktree");
+    command.arg("remove");
+    // Locked worktrees need to be forced twice
+    command.arg_if("--force", force).arg_if("--force", force);
+    command
+        .arg("--")
+        .arg(&path)
+        .run(&app_handle, GitCommandType::Mutation)
+        .await?;
+
+    if let Some(repository) = db.get_repository_by_path(&path).await? {
+        db.remove_repository(repository.id).await?;
+    }
+
+    Ok(())
+}
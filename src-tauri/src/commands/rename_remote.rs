@@ -0,0 +1,22 @@
+use crate::{cli::GitCommand, db::GitCommandType};
+
+use super::CommandResult;
+
+/// Rename a remote, along with its remote-tracking branches and upstream config
+#[tauri::command]
+#[specta::specta]
+pub async fn rename_remote(
+    app_handle: tauri::AppHandle,
+    old_name: String,
+    new_name: String,
+) -> CommandResult<()> {
+    GitCommand::new("remote")
+        .arg("rename")
+        .arg("--")
+        .arg(old_name)
+        .arg(new_name)
+        .run(&app_handle, GitCommandType::Mutation)
+        .await?;
+
+    Ok(())
+}
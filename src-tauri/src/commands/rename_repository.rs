@@ -0,0 +1,20 @@
+use crate::{
+    commands::{CommandError, CommandResult},
+    db::{Db, Repository},
+};
+
+/// Change the name a repository is shown with
+#[tauri::command]
+#[specta::specta]
+pub async fn rename_repository(
+    db: tauri::State<'_, Db>,
+    id: i64,
+    name: String,
+) -> CommandResult<Repository> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(CommandError::Other("Repository name can't be empty".into()));
+    }
+
+    Ok(db.rename_repository(id, name).await?)
+}
@@ -0,0 +1,8 @@
+use crate::{commands::CommandResult, db::Db};
+
+/// Set the order repositories are listed in, within pinned and unpinned repositories
+#[tauri::command]
+#[specta::specta]
+pub async fn reorder_repositories(db: tauri::State<'_, Db>, ids: Vec<i64>) -> CommandResult<()> {
+    Ok(db.reorder_repositories(&ids).await?)
+}
@@ -0,0 +1,23 @@
+use crate::{
+    commands::{CommandError, CommandResult},
+    db::Db,
+};
+
+/// Set how many commands of each type to keep in a repository's log
+#[tauri::command]
+#[specta::specta]
+pub async fn set_git_command_log_retention(
+    db: tauri::State<'_, Db>,
+    repository_id: i64,
+    retention: u32,
+) -> CommandResult<()> {
+    if retention == 0 {
+        return Err(CommandError::Other(
+            "Log retention must be at least 1".into(),
+        ));
+    }
+
+    Ok(db
+        .set_git_command_log_retention(repository_id, retention.into())
+        .await?)
+}
@@ -1,9 +1,17 @@
-use crate::{commands::CommandResult, db::Db};
+use crate::{commands::CommandResult, db::Db, watcher::RepositoryWatcher};
 
 #[tauri::command]
 #[specta::specta]
-pub async fn set_open_repository(db: tauri::State<'_, Db>, id: Option<i64>) -> CommandResult<()> {
+pub async fn set_open_repository(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    watcher: tauri::State<'_, RepositoryWatcher>,
+    id: Option<i64>,
+) -> CommandResult<()> {
     db.set_open_repository(id).await?;
 
+    let open_repository = db.state.lock().await.open_repository.clone();
+    watcher.watch(&app_handle, open_repository.as_ref())?;
+
     Ok(())
 }
@@ -0,0 +1,24 @@
+use crate::{cli::GitCommand, db::GitCommandType};
+
+use super::CommandResult;
+
+/// Replace the URL of a remote. If `push` is set, only the push URL is changed.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_remote_url(
+    app_handle: tauri::AppHandle,
+    name: String,
+    url: String,
+    push: bool,
+) -> CommandResult<()> {
+    GitCommand::new("remote")
+        .arg("set-url")
+        .arg_if("--push", push)
+        .arg("--")
+        .arg(name)
+        .arg(url)
+        .run(&app_handle, GitCommandType::Mutation)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,15 @@
+use crate::{
+    commands::CommandResult,
+    db::{Db, Repository},
+};
+
+/// Pinned repositories are listed before the others
+#[tauri::command]
+#[specta::specta]
+pub async fn set_repository_pinned(
+    db: tauri::State<'_, Db>,
+    id: i64,
+    pinned: bool,
+) -> CommandResult<Repository> {
+    Ok(db.set_repository_pinned(id, pinned).await?)
+}
@@ -0,0 +1,25 @@
+use crate::{cli::GitCommand, db::GitCommandType};
+
+use super::CommandResult;
+
+/// Update the remote URLs of submodules after they change in `.gitmodules`, all of them if
+/// `paths` is empty
+#[tauri::command]
+#[specta::specta]
+pub async fn sync_submodules(
+    app_handle: tauri::AppHandle,
+    paths: Vec<String>,
+    recursive: bool,
+) -> CommandResult<()> {
+    let mut command = GitCommand::new("submodule");
+    command
+        .arg("sync")
+        .arg_if("--recursive", recursive)
+        .arg("--");
+    for path in paths {
+        command.arg(path);
+    }
+    command.run(&app_handle, GitCommandType::Mutation).await?;
+
+    Ok(())
+}
@@ -0,0 +1,16 @@
+use crate::{cli::GitCommand, db::GitCommandType};
+
+use super::CommandResult;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn unlock_worktree(app_handle: tauri::AppHandle, path: String) -> CommandResult<()> {
+    GitCommand::new("worktree")
+        .arg("unlock")
+        .arg("--")
+        .arg(path)
+        .run(&app_handle, GitCommandType::Mutation)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use tauri_specta::Event;
+
+use crate::{
+    commands::{CommandError, CommandResult},
+    db::Db,
+    settings::{Settings, SettingsChangedEvent},
+};
+
+/// Replace all settings and check git again, emitting a [`SettingsChangedEvent`]
+#[tauri::command]
+#[specta::specta]
+pub async fn update_settings(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Db>,
+    settings: Settings,
+) -> CommandResult<Settings> {
+    settings.validate().map_err(CommandError::Other)?;
+    db.update_settings(settings.clone()).await?;
+    // The executable or environment may have changed
+    db.check_git_version().await;
+
+    SettingsChangedEvent(settings.clone())
+        .emit(&app_handle)
+        .expect("Failed to emit event");
+
+    Ok(settings)
+}
@@ -0,0 +1,40 @@
+use serde::Deserialize;
+use specta::Type;
+
+use crate::{cli::GitCommand, db::GitCommandType};
+
+use super::CommandResult;
+
+#[derive(Debug, Deserialize, Type)]
+pub struct UpdateSubmodulesOptions {
+    /// Initialize submodules that haven't been yet
+    pub init: bool,
+    /// Also update submodules inside the submodules
+    pub recursive: bool,
+    /// Check out the latest commit of each submodule's remote branch instead of the recorded one
+    pub remote: bool,
+}
+
+/// Clone missing submodules and check out their recorded commits, all of them if `paths` is empty
+#[tauri::command]
+#[specta::specta]
+pub async fn update_submodules(
+    app_handle: tauri::AppHandle,
+    paths: Vec<String>,
+    options: UpdateSubmodulesOptions,
+) -> CommandResult<()> {
+    let mut command = GitCommand::new("submodule");
+    command
+        .arg("update")
+        .progress()
+        .arg_if("--init", options.init)
+        .arg_if("--recursive", options.recursive)
+        .arg_if("--remote", options.remote)
+        .arg("--");
+    for path in paths {
+        command.arg(path);
+    }
+    command.run(&app_handle, GitCommandType::Mutation).await?;
+
+    Ok(())
+}
@@ -1,12 +1,18 @@
-use std::path::PathBuf;
+use std::time::Duration;
 
 use chrono::NaiveDateTime;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use specta::Type;
 use sqlx::{prelude::FromRow, sqlite::SqliteRow, Pool, QueryBuilder, Row, Sqlite};
 use tokio::sync::Mutex;
 
-#[derive(Serialize, Clone, Type, Debug)]
+use crate::{
+    cli::{check_git_version, GitVersionCheck},
+    settings::Settings,
+    structures::repository_status::RepositoryStatus,
+};
+
+#[derive(Serialize, Deserialize, Clone, Type, Debug)]
 pub struct Repository {
     pub id: i64,
     pub name: String,
@@ -15,6 +21,19 @@ pub struct Repository {
     pub last_opened_at: Option<NaiveDateTime>,
     pub last_fetched_at: Option<NaiveDateTime>,
     pub has_changes: bool,
+    /// Number of logged commands of each type to keep
+    pub git_command_log_retention: i64,
+    /// Commits the current branch is ahead of its upstream, None if it has no upstream
+    pub ahead: Option<i64>,
+    /// Commits the current branch is behind its upstream, None if it has no upstream
+    pub behind: Option<i64>,
+    /// When `has_changes`, `ahead` and `behind` were last updated
+    pub last_checked_at: Option<NaiveDateTime>,
+    /// Pinned repositories are listed first
+    pub pinned: bool,
+    pub sort_order: i64,
+    /// Set if this is a linked worktree of another added repository
+    pub main_repository_id: Option<i64>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Type)]
@@ -34,12 +53,92 @@ impl From<i64> for GitCommandType {
     }
 }
 
+/// How a logged command finished
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Type, PartialEq, Eq)]
+#[repr(i64)]
+pub enum GitCommandOutcome {
+    Success,
+    Cancelled,
+    TimedOut,
+    /// Git exited with an error
+    Failed,
+}
+
+impl From<i64> for GitCommandOutcome {
+    fn from(value: i64) -> Self {
+        match value {
+            0 => Self::Success,
+            1 => Self::Cancelled,
+            2 => Self::TimedOut,
+            3 => Self::Failed,
+            _ => panic!("Not a valid git command outcome"),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Type, Debug)]
 pub struct GitCommandLog {
     pub id: i64,
     pub command: String,
+    pub args: GitCommandArgs,
     pub command_type: GitCommandType,
     pub created_at: NaiveDateTime,
+    /// Final progress of each phase, one per line, if the command reported progress
+    pub progress: Option<String>,
+    pub outcome: GitCommandOutcome,
+    /// None if git didn't exit by itself, e.g. it was cancelled
+    pub exit_code: Option<i64>,
+    pub duration_ms: i64,
+    /// None for commands logged before this was recorded
+    pub working_directory: Option<String>,
+    /// Truncated to [`MAX_LOG_OUTPUT_LEN`]
+    pub stdout: Option<String>,
+    /// Truncated to [`MAX_LOG_OUTPUT_LEN`]
+    pub stderr: Option<String>,
+}
+
+/// Arguments passed to git, starting with the command.
+/// None for commands logged before these were recorded.
+#[derive(Serialize, Deserialize, Clone, Type, Debug, Default, PartialEq, Eq)]
+pub struct GitCommandArgs(pub Option<Vec<String>>);
+
+/// Decode the JSON array stored in the `args` column
+impl From<Option<String>> for GitCommandArgs {
+    fn from(value: Option<String>) -> Self {
+        Self(value.and_then(|args| serde_json::from_str(&args).ok()))
+    }
+}
+
+/// Maximum number of bytes of stdout and stderr to store for each command
+pub const MAX_LOG_OUTPUT_LEN: usize = 4096;
+
+/// Details of a finished command to add to the log
+pub struct NewGitCommandLog {
+    pub command: String,
+    pub args: Vec<String>,
+    pub command_type: GitCommandType,
+    pub progress: Option<String>,
+    pub outcome: GitCommandOutcome,
+    pub exit_code: Option<i32>,
+    pub duration: Duration,
+    pub working_directory: String,
+    /// Use [`truncate_output`] to keep the log small
+    pub stdout: Option<String>,
+    pub stderr: Option<String>,
+}
+
+/// Decode command output for the log, keeping at most [`MAX_LOG_OUTPUT_LEN`] bytes
+pub fn truncate_output(output: &[u8]) -> String {
+    let output = String::from_utf8_lossy(output);
+    if output.len() <= MAX_LOG_OUTPUT_LEN {
+        return output.into();
+    }
+    // Don't cut a character in half
+    let end = (0..=MAX_LOG_OUTPUT_LEN)
+        .rev()
+        .find(|&index| output.is_char_boundary(index))
+        .unwrap_or_default();
+    format!("{}…", &output[..end])
 }
 
 impl<'r> FromRow<'r, SqliteRow> for GitCommandLog {
@@ -47,15 +146,79 @@ impl<'r> FromRow<'r, SqliteRow> for GitCommandLog {
         Ok(Self {
             id: row.try_get("id")?,
             command: row.try_get("command")?,
+            args: row.try_get::<Option<String>, _>("args")?.into(),
             command_type: row.try_get::<i64, _>("command_type")?.into(),
             created_at: row.try_get("created_at")?,
+            progress: row.try_get("progress")?,
+            outcome: row.try_get::<i64, _>("outcome")?.into(),
+            exit_code: row.try_get("exit_code")?,
+            duration_ms: row.try_get("duration_ms")?,
+            working_directory: row.try_get("working_directory")?,
+            stdout: row.try_get("stdout")?,
+            stderr: row.try_get("stderr")?,
         })
     }
 }
 
+/// Position in the command log to continue a page from
+#[derive(Serialize, Deserialize, Clone, Type, Debug)]
+pub struct GitCommandLogCursor {
+    pub created_at: NaiveDateTime,
+    pub id: i64,
+}
+
+/// Entries of the command log, newest first
+#[derive(Serialize, Clone, Type, Debug)]
+pub struct GitCommandLogPage {
+    pub entries: Vec<GitCommandLog>,
+    /// None if there are no older entries
+    pub next_cursor: Option<GitCommandLogCursor>,
+}
+
+impl GitCommandLogPage {
+    /// Create a page from up to `limit + 1` entries, where the extra entry means there's another page
+    fn new(mut entries: Vec<GitCommandLog>, limit: i64) -> Self {
+        let next_cursor = if entries.len() as i64 > limit {
+            entries.truncate(limit as usize);
+            entries.last().map(|last| GitCommandLogCursor {
+                created_at: last.created_at,
+                id: last.id,
+            })
+        } else {
+            None
+        };
+
+        Self {
+            entries,
+            next_cursor,
+        }
+    }
+}
+
+/// Escape `%`, `_` and `\` so `search` matches literally in a `LIKE ... ESCAPE '\'` pattern
+fn like_pattern(search: &str) -> String {
+    let mut pattern = String::from("%");
+    for c in search.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    pattern
+}
+
+/// Decode a column that's stored as JSON
+fn decode_json<T: DeserializeOwned>(value: &str) -> Result<T, sqlx::Error> {
+    serde_json::from_str(value).map_err(|err| sqlx::Error::Decode(err.into()))
+}
+
 #[derive(Serialize, Clone, Default, Type)]
 pub struct State {
     pub open_repository: Option<Repository>,
+    pub settings: Settings,
+    /// None until git has been checked at startup
+    pub git_version: Option<GitVersionCheck>,
 }
 
 pub struct Db {
@@ -81,36 +244,239 @@ impl Db {
             .await
             .ok();
 
+        let settings = sqlx::query!("SELECT git_command_log_retention, graph_page_size, auto_fetch_interval_minutes, auto_fetch_all_repositories, diff_layout, max_highlight_size, max_image_size, git_executable, git_environment, git_config_overrides, protected_branches, git_query_timeout_seconds, git_mutation_timeout_seconds FROM settings WHERE id = 0")
+            .fetch_one(&self.pool)
+            .await?;
+        state.settings = Settings {
+            git_command_log_retention: settings.git_command_log_retention,
+            graph_page_size: settings.graph_page_size,
+            auto_fetch_interval_minutes: settings.auto_fetch_interval_minutes,
+            auto_fetch_all_repositories: settings.auto_fetch_all_repositories,
+            diff_layout: settings
+                .diff_layout
+                .try_into()
+                .map_err(|err: String| sqlx::Error::Decode(err.into()))?,
+            max_highlight_size: settings.max_highlight_size,
+            max_image_size: settings.max_image_size,
+            git_executable: settings.git_executable,
+            git_environment: decode_json(&settings.git_environment)?,
+            git_config_overrides: decode_json(&settings.git_config_overrides)?,
+            protected_branches: decode_json(&settings.protected_branches)?,
+            git_query_timeout_seconds: settings.git_query_timeout_seconds,
+            git_mutation_timeout_seconds: settings.git_mutation_timeout_seconds,
+        };
+
+        Ok(())
+    }
+
+    /// Check that the configured git executable can be used, storing the result
+    pub async fn check_git_version(&self) -> GitVersionCheck {
+        let settings = self.get_settings().await;
+        let check = check_git_version(&settings).await;
+        self.state.lock().await.git_version = Some(check.clone());
+        check
+    }
+
+    pub async fn get_settings(&self) -> Settings {
+        self.state.lock().await.settings.clone()
+    }
+
+    pub async fn update_settings(&self, settings: Settings) -> Result<(), sqlx::Error> {
+        let mut state = self.state.lock().await;
+
+        let diff_layout = settings.diff_layout as i64;
+        let git_environment = serde_json::to_string(&settings.git_environment)
+            .expect("Failed to serialize environment variables");
+        let git_config_overrides = serde_json::to_string(&settings.git_config_overrides)
+            .expect("Failed to serialize config overrides");
+        let protected_branches = serde_json::to_string(&settings.protected_branches)
+            .expect("Failed to serialize protected branches");
+        sqlx::query!(
+            "UPDATE settings SET git_command_log_retention = ?, graph_page_size = ?, auto_fetch_interval_minutes = ?, auto_fetch_all_repositories = ?, diff_layout = ?, max_highlight_size = ?, max_image_size = ?, git_executable = ?, git_environment = ?, git_config_overrides = ?, protected_branches = ?, git_query_timeout_seconds = ?, git_mutation_timeout_seconds = ? WHERE id = 0",
+            settings.git_command_log_retention,
+            settings.graph_page_size,
+            settings.auto_fetch_interval_minutes,
+            settings.auto_fetch_all_repositories,
+            diff_layout,
+            settings.max_highlight_size,
+            settings.max_image_size,
+            settings.git_executable,
+            git_environment,
+            git_config_overrides,
+            protected_branches,
+            settings.git_query_timeout_seconds,
+            settings.git_mutation_timeout_seconds
+        )
+        .execute(&self.pool)
+        .await?;
+
+        state.settings = settings;
+
         Ok(())
     }
 
-    pub async fn add_repository(&self, local_path: PathBuf) -> Result<Repository, sqlx::Error> {
-        let name = local_path
-            .file_name()
-            .map(|name| name.to_str())
-            .expect("Couldn't get folder name from path");
-        let local_path = local_path.to_str();
+    /// Add a repository to the end of the list. Fails if `local_path` has already been added.
+    pub async fn add_repository(
+        &self,
+        name: &str,
+        local_path: &str,
+    ) -> Result<Repository, sqlx::Error> {
+        let retention = self.state.lock().await.settings.git_command_log_retention;
 
         let id = sqlx::query!(
-            "INSERT INTO repository (name, local_path) VALUES (?, ?)",
+            "INSERT INTO repository (name, local_path, git_command_log_retention, sort_order) VALUES (?, ?, ?, (SELECT COALESCE(MAX(sort_order), 0) + 1 FROM repository))",
             name,
-            local_path
+            local_path,
+            retention
         )
         .execute(&self.pool)
         .await?
         .last_insert_rowid();
 
-        let repository = sqlx::query_as!(Repository, "SELECT * FROM repository WHERE id = ?", id)
+        self.get_repository(id).await
+    }
+
+    pub async fn get_repository(&self, id: i64) -> Result<Repository, sqlx::Error> {
+        sqlx::query_as!(Repository, "SELECT * FROM repository WHERE id = ?", id)
             .fetch_one(&self.pool)
+            .await
+    }
+
+    pub async fn get_repository_by_path(
+        &self,
+        local_path: &str,
+    ) -> Result<Option<Repository>, sqlx::Error> {
+        sqlx::query_as!(
+            Repository,
+            "SELECT * FROM repository WHERE local_path = ?",
+            local_path
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Link the added repositories at `worktree_paths` to the one at `main_path`, if it's been added
+    pub async fn link_worktrees(
+        &self,
+        main_path: &str,
+        worktree_paths: &[String],
+    ) -> Result<(), sqlx::Error> {
+        let mut transaction = self.pool.begin().await?;
+        for worktree_path in worktree_paths {
+            sqlx::query!(
+                "UPDATE repository SET main_repository_id = (SELECT id FROM repository WHERE local_path = ?) WHERE local_path = ?",
+                main_path,
+                worktree_path
+            )
+            .execute(&mut *transaction)
             .await?;
+        }
+        transaction.commit().await?;
 
-        Ok(repository)
+        let open_repository_id = self
+            .state
+            .lock()
+            .await
+            .open_repository
+            .as_ref()
+            .map(|repo| repo.id);
+        if let Some(id) = open_repository_id {
+            self.refresh_repository(id).await?;
+        }
+
+        Ok(())
     }
 
+    /// Pinned repositories first, then in the order set with [`Self::reorder_repositories`]
     pub async fn get_repositories(&self) -> Result<Vec<Repository>, sqlx::Error> {
-        sqlx::query_as!(Repository, "SELECT * FROM repository")
-            .fetch_all(&self.pool)
-            .await
+        sqlx::query_as!(
+            Repository,
+            "SELECT * FROM repository ORDER BY pinned DESC, sort_order ASC, id ASC"
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Remove a repository and its command log, closing it if it's open
+    pub async fn remove_repository(&self, id: i64) -> Result<(), sqlx::Error> {
+        let mut state = self.state.lock().await;
+
+        // The command log and open repository state are cleaned up by foreign keys
+        sqlx::query!("DELETE FROM repository WHERE id = ?", id)
+            .execute(&self.pool)
+            .await?;
+
+        if state
+            .open_repository
+            .as_ref()
+            .is_some_and(|repo| repo.id == id)
+        {
+            state.open_repository = None;
+        }
+
+        Ok(())
+    }
+
+    pub async fn rename_repository(&self, id: i64, name: &str) -> Result<Repository, sqlx::Error> {
+        sqlx::query!("UPDATE repository SET name = ? WHERE id = ?", name, id)
+            .execute(&self.pool)
+            .await?;
+        self.refresh_repository(id).await
+    }
+
+    /// Update the path of a repository that was moved. Fails if `local_path` has already been added.
+    pub async fn relocate_repository(
+        &self,
+        id: i64,
+        local_path: &str,
+    ) -> Result<Repository, sqlx::Error> {
+        sqlx::query!(
+            "UPDATE repository SET local_path = ? WHERE id = ?",
+            local_path,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+        self.refresh_repository(id).await
+    }
+
+    pub async fn set_repository_pinned(
+        &self,
+        id: i64,
+        pinned: bool,
+    ) -> Result<Repository, sqlx::Error> {
+        sqlx::query!("UPDATE repository SET pinned = ? WHERE id = ?", pinned, id)
+            .execute(&self.pool)
+            .await?;
+        self.refresh_repository(id).await
+    }
+
+    /// Set the sort order of repositories to the order of `ids`
+    pub async fn reorder_repositories(&self, ids: &[i64]) -> Result<(), sqlx::Error> {
+        let mut transaction = self.pool.begin().await?;
+        for (sort_order, id) in ids.iter().enumerate() {
+            let sort_order = sort_order as i64;
+            sqlx::query!(
+                "UPDATE repository SET sort_order = ? WHERE id = ?",
+                sort_order,
+                id
+            )
+            .execute(&mut *transaction)
+            .await?;
+        }
+        transaction.commit().await
+    }
+
+    /// Get a repository after it's updated, keeping the in-memory open repository in sync
+    async fn refresh_repository(&self, id: i64) -> Result<Repository, sqlx::Error> {
+        let mut state = self.state.lock().await;
+        let repository = self.get_repository(id).await?;
+
+        if let Some(open_repository) = state.open_repository.as_mut().filter(|repo| repo.id == id) {
+            *open_repository = repository.clone();
+        }
+
+        Ok(repository)
     }
 
     pub async fn set_open_repository(&self, id: Option<i64>) -> Result<(), sqlx::Error> {
@@ -150,79 +516,382 @@ impl Db {
         Ok(())
     }
 
-    /// Adds a git command log to the currently open repository
+    /// Adds a git command log to the repository the command was run in
     pub async fn add_git_command_log(
         &self,
-        command: String,
-        command_type: GitCommandType,
+        repository_id: i64,
+        log: NewGitCommandLog,
     ) -> Result<GitCommandLog, sqlx::Error> {
-        let state = self.state.lock().await;
+        let command_type = log.command_type as i64;
+        let outcome = log.outcome as i64;
+        let duration_ms = log.duration.as_millis() as i64;
+        let args = serde_json::to_string(&log.args).expect("Failed to serialize command arguments");
+        let id = sqlx::query!(
+            "INSERT INTO git_command_log (command, args, command_type, repository_id, progress, outcome, exit_code, duration_ms, working_directory, stdout, stderr) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            log.command,
+            args,
+            command_type,
+            repository_id,
+            log.progress,
+            outcome,
+            log.exit_code,
+            duration_ms,
+            log.working_directory,
+            log.stdout,
+            log.stderr
+        )
+        .execute(&self.pool)
+        .await?
+        .last_insert_rowid();
 
-        match &state.open_repository {
-            Some(open_repository) => {
-                let command_type = command_type as i64;
-                let id = sqlx::query!(
-                    "INSERT INTO git_command_log (command, command_type, repository_id) VALUES (?, ?, ?)",
-                    command,
-                    command_type,
-                    open_repository.id
-                )
-                .execute(&self.pool)
-                .await?
-                .last_insert_rowid();
-
-                // Limit the number of logs of each type stored in the database
-                let count = sqlx::query!(
-                    "SELECT COUNT(*) as count FROM git_command_log WHERE command_type = ?",
-                    command_type
-                )
-                .fetch_one(&self.pool)
-                .await?
-                .count;
-
-                // TODO: Use a configurable value for the maximum
-                if count > 200 {
-                    let amount_to_delete = count - 200;
-                    sqlx::query!(
-                        "DELETE FROM git_command_log WHERE id IN (SELECT id FROM git_command_log WHERE command_type = ? ORDER BY created_at ASC LIMIT ?)",
-                        command_type,
-                        amount_to_delete
-                    )
-                    .execute(&self.pool)
-                    .await?;
-                }
+        // Fetched before pruning, which would delete it if the repository keeps no logs
+        let git_command_log = sqlx::query_as!(
+            GitCommandLog,
+            "SELECT id, command, args, command_type, created_at, progress, outcome, exit_code, duration_ms, working_directory, stdout, stderr FROM git_command_log WHERE id = ?",
+            id
+        )
+        .fetch_one(&self.pool)
+        .await?;
 
-                sqlx::query_as!(
-                    GitCommandLog,
-                    "SELECT id, command, command_type, created_at FROM git_command_log WHERE id = ?",
-                    id
-                )
-                .fetch_one(&self.pool)
-                .await
-            }
-            None => panic!("TODO: handle no open repo"),
+        let retention = sqlx::query!(
+            "SELECT git_command_log_retention FROM repository WHERE id = ?",
+            repository_id
+        )
+        .fetch_one(&self.pool)
+        .await?
+        .git_command_log_retention;
+        self.prune_git_command_log(repository_id, command_type, retention)
+            .await?;
+
+        Ok(git_command_log)
+    }
+
+    /// Store the result of checking a repository's status
+    pub async fn set_repository_status(
+        &self,
+        repository_id: i64,
+        status: &RepositoryStatus,
+    ) -> Result<Repository, sqlx::Error> {
+        let mut state = self.state.lock().await;
+
+        let repository = sqlx::query_as!(
+            Repository,
+            "UPDATE repository SET has_changes = ?, ahead = ?, behind = ?, last_checked_at = CURRENT_TIMESTAMP WHERE id = ? RETURNING *",
+            status.has_changes,
+            status.ahead,
+            status.behind,
+            repository_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        if let Some(open_repository) = state
+            .open_repository
+            .as_mut()
+            .filter(|repo| repo.id == repository_id)
+        {
+            *open_repository = repository.clone();
+        }
+
+        Ok(repository)
+    }
+
+    /// Record that a repository was just fetched
+    pub async fn set_last_fetched_at(&self, repository_id: i64) -> Result<(), sqlx::Error> {
+        let mut state = self.state.lock().await;
+
+        let last_fetched_at = sqlx::query!(
+            "UPDATE repository SET last_fetched_at = CURRENT_TIMESTAMP WHERE id = ? RETURNING last_fetched_at",
+            repository_id
+        )
+        .fetch_one(&self.pool)
+        .await?
+        .last_fetched_at;
+
+        if let Some(open_repository) = state
+            .open_repository
+            .as_mut()
+            .filter(|repo| repo.id == repository_id)
+        {
+            open_repository.last_fetched_at = last_fetched_at;
+        }
+
+        Ok(())
+    }
+
+    /// Delete all but the newest `retention` logs of a command type for a repository
+    async fn prune_git_command_log(
+        &self,
+        repository_id: i64,
+        command_type: i64,
+        retention: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "DELETE FROM git_command_log WHERE repository_id = ? AND command_type = ? AND id NOT IN (SELECT id FROM git_command_log WHERE repository_id = ? AND command_type = ? ORDER BY created_at DESC, id DESC LIMIT ?)",
+            repository_id,
+            command_type,
+            repository_id,
+            command_type,
+            retention
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Set how many logs of each command type to keep for a repository, deleting any extra logs
+    pub async fn set_git_command_log_retention(
+        &self,
+        repository_id: i64,
+        retention: i64,
+    ) -> Result<(), sqlx::Error> {
+        let mut state = self.state.lock().await;
+
+        sqlx::query!(
+            "UPDATE repository SET git_command_log_retention = ? WHERE id = ?",
+            retention,
+            repository_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        for command_type in [GitCommandType::Query, GitCommandType::Mutation] {
+            self.prune_git_command_log(repository_id, command_type as i64, retention)
+                .await?;
         }
+
+        if let Some(open_repository) = state
+            .open_repository
+            .as_mut()
+            .filter(|repo| repo.id == repository_id)
+        {
+            open_repository.git_command_log_retention = retention;
+        }
+
+        Ok(())
     }
 
-    /// Get the full git command log history for the open repository
+    /// Get a page of the git command log for the open repository, newest first
     pub async fn get_git_command_log(
         &self,
         filter: Option<GitCommandType>,
-    ) -> Result<Vec<GitCommandLog>, sqlx::Error> {
+        search: Option<String>,
+        cursor: Option<GitCommandLogCursor>,
+        limit: i64,
+    ) -> Result<GitCommandLogPage, sqlx::Error> {
         let state = self.state.lock().await;
 
         match &state.open_repository.as_ref().map(|r| r.id) {
             Some(open_repository_id) => {
-                // TODO: sort by date, and paginate
-                let mut query = QueryBuilder::new("SELECT id, command, command_type, created_at FROM git_command_log WHERE repository_id = ");
+                let mut query = QueryBuilder::new("SELECT id, command, args, command_type, created_at, progress, outcome, exit_code, duration_ms, working_directory, stdout, stderr FROM git_command_log WHERE repository_id = ");
                 query.push_bind(open_repository_id);
                 if let Some(command_type) = filter {
                     query.push(" AND command_type = ");
                     query.push_bind(command_type as i64);
                 }
-                query.build_query_as().fetch_all(&self.pool).await
+                if let Some(search) = search.filter(|search| !search.is_empty()) {
+                    query.push(" AND command LIKE ");
+                    query.push_bind(like_pattern(&search));
+                    query.push(" ESCAPE '\\'");
+                }
+                if let Some(cursor) = cursor {
+                    query.push(" AND (created_at, id) < (");
+                    query.push_bind(cursor.created_at);
+                    query.push(", ");
+                    query.push_bind(cursor.id);
+                    query.push(")");
+                }
+                // Fetch one extra entry to know if there's another page
+                query.push(" ORDER BY created_at DESC, id DESC LIMIT ");
+                query.push_bind(limit + 1);
+
+                let entries = query.build_query_as().fetch_all(&self.pool).await?;
+                Ok(GitCommandLogPage::new(entries, limit))
             }
             None => panic!("TODO: handle no open repo"),
         }
     }
+
+    /// Get every logged command for a repository, oldest first
+    pub async fn get_full_git_command_log(
+        &self,
+        repository_id: i64,
+    ) -> Result<Vec<GitCommandLog>, sqlx::Error> {
+        sqlx::query_as!(
+            GitCommandLog,
+            "SELECT id, command, args, command_type, created_at, progress, outcome, exit_code, duration_ms, working_directory, stdout, stderr FROM git_command_log WHERE repository_id = ? ORDER BY created_at ASC, id ASC",
+            repository_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    use super::{
+        like_pattern, truncate_output, Db, GitCommandArgs, GitCommandLog, GitCommandLogPage,
+        GitCommandOutcome, GitCommandType, NewGitCommandLog, MAX_LOG_OUTPUT_LEN,
+    };
+
+    async fn test_db() -> Db {
+        // Each connection to an in-memory database gets its own database
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+        let db = Db::new(pool);
+        db.load().await.unwrap();
+        db
+    }
+
+    fn entry(id: i64) -> GitCommandLog {
+        GitCommandLog {
+            id,
+            command: "status".into(),
+            args: GitCommandArgs(Some(vec!["status".into()])),
+            command_type: GitCommandType::Query,
+            created_at: "2026-10-19T12:00:00".parse().unwrap(),
+            progress: None,
+            outcome: GitCommandOutcome::Success,
+            exit_code: Some(0),
+            duration_ms: 0,
+            working_directory: None,
+            stdout: None,
+            stderr: None,
+        }
+    }
+
+    #[test]
+    fn escapes_like_patterns() {
+        assert_eq!(like_pattern("fetch"), "%fetch%");
+        assert_eq!(like_pattern("100%_done\\"), "%100\\%\\_done\\\\%");
+    }
+
+    #[test]
+    fn paginates_with_cursor() {
+        let page = GitCommandLogPage::new(vec![entry(5), entry(4), entry(3)], 2);
+        assert_eq!(
+            page.entries
+                .iter()
+                .map(|entry| entry.id)
+                .collect::<Vec<_>>(),
+            vec![5, 4]
+        );
+        let cursor = page.next_cursor.unwrap();
+        assert_eq!(cursor.id, 4);
+        assert_eq!(cursor.created_at, page.entries[1].created_at);
+
+        let page = GitCommandLogPage::new(vec![entry(2), entry(1)], 2);
+        assert_eq!(page.entries.len(), 2);
+        assert!(page.next_cursor.is_none());
+    }
+
+    #[test]
+    fn decodes_stored_args() {
+        assert_eq!(
+            GitCommandArgs::from(Some(r#"["log","--format=%H\u0001%s"]"#.into())),
+            GitCommandArgs(Some(vec!["log".into(), "--format=%H\x01%s".into()]))
+        );
+        assert_eq!(GitCommandArgs::from(None), GitCommandArgs(None));
+    }
+
+    #[test]
+    fn truncates_output_at_char_boundaries() {
+        assert_eq!(truncate_output(b"short"), "short");
+        assert_eq!(truncate_output(b"invalid \xff"), "invalid \u{fffd}");
+
+        let exact = "a".repeat(MAX_LOG_OUTPUT_LEN);
+        assert_eq!(truncate_output(exact.as_bytes()), exact);
+
+        // The limit falls in the middle of the last `é`, which takes 2 bytes
+        let output = format!("a{}", "é".repeat(MAX_LOG_OUTPUT_LEN / 2));
+        let truncated = truncate_output(output.as_bytes());
+        assert_eq!(
+            truncated,
+            format!("a{}…", "é".repeat(MAX_LOG_OUTPUT_LEN / 2 - 1))
+        );
+        assert!(!truncated.contains('\u{fffd}'));
+    }
+
+    #[tokio::test]
+    async fn logs_failed_commands() {
+        let db = test_db().await;
+        let repository = db.add_repository("repo", "/repo").await.unwrap();
+
+        let log = db
+            .add_git_command_log(
+                repository.id,
+                NewGitCommandLog {
+                    command: "checkout missing".into(),
+                    args: vec!["checkout".into(), "missing".into()],
+                    command_type: GitCommandType::Mutation,
+                    progress: None,
+                    outcome: GitCommandOutcome::Failed,
+                    exit_code: Some(1),
+                    duration: Duration::from_millis(15),
+                    working_directory: "/repo".into(),
+                    stdout: Some(truncate_output(b"")),
+                    stderr: Some(truncate_output(
+                        b"error: pathspec 'missing' did not match any file(s) known to git\n",
+                    )),
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(log.outcome, GitCommandOutcome::Failed);
+        assert_eq!(log.exit_code, Some(1));
+        assert_eq!(log.duration_ms, 15);
+        assert_eq!(
+            log.args,
+            GitCommandArgs(Some(vec!["checkout".into(), "missing".into()]))
+        );
+        assert_eq!(
+            log.stderr.as_deref(),
+            Some("error: pathspec 'missing' did not match any file(s) known to git\n")
+        );
+    }
+
+    #[tokio::test]
+    async fn logs_commands_in_repositories_that_keep_none() {
+        let db = test_db().await;
+        let repository = db.add_repository("repo", "/repo").await.unwrap();
+        db.set_git_command_log_retention(repository.id, 0)
+            .await
+            .unwrap();
+
+        let log = db
+            .add_git_command_log(
+                repository.id,
+                NewGitCommandLog {
+                    command: "status".into(),
+                    args: vec!["status".into()],
+                    command_type: GitCommandType::Query,
+                    progress: None,
+                    outcome: GitCommandOutcome::Success,
+                    exit_code: Some(0),
+                    duration: Duration::from_millis(5),
+                    working_directory: "/repo".into(),
+                    stdout: None,
+                    stderr: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(log.command, "status");
+        assert!(db
+            .get_full_git_command_log(repository.id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
 }
@@ -3,35 +3,100 @@
 
 use std::{fs::create_dir_all, str::FromStr};
 
-use cli::GitCommandEvent;
+use auto_fetch::RepositoryFetchedEvent;
+use cli::{GitCommandEvent, GitCommandRegistry, GitProgressEvent};
+use commands::{
+    add_repository_from_path::normalize_repository_paths, get_file_diff::MovedBlockCache,
+};
 use db::Db;
+use settings::SettingsChangedEvent;
 use specta_typescript::Typescript;
 use sqlx::{sqlite::SqliteConnectOptions, SqlitePool};
+use status_check::RepositoryStatusEvent;
 use tauri::{Manager, RunEvent};
 use tauri_specta::{collect_commands, collect_events, Builder};
+use watcher::{
+    HeadMovedEvent, RefsChangedEvent, RepositoryWatchFailedEvent, RepositoryWatcher,
+    WorkingTreeChangedEvent,
+};
 
+pub mod auto_fetch;
 pub mod cli;
 pub mod commands;
 pub mod db;
+pub mod settings;
+pub mod status_check;
 pub mod structures;
 pub mod utils;
+pub mod watcher;
 
 fn main() {
     let builder = Builder::<tauri::Wry>::new()
         .commands(collect_commands![
             commands::get_branches::get_branches,
+            commands::get_branch_cleanup_candidates::get_branch_cleanup_candidates,
+            commands::delete_branches::delete_branches,
             commands::add_repository_from_path::add_repository_from_path,
+            commands::clone_repository::clone_repository,
+            commands::init_repository::init_repository,
             commands::set_open_repository::set_open_repository,
             commands::get_open_repository::get_open_repository,
             commands::get_repositories::get_repositories,
+            commands::get_repository_watch_failure::get_repository_watch_failure,
+            commands::remove_repository::remove_repository,
+            commands::rename_repository::rename_repository,
+            commands::relocate_repository::relocate_repository,
+            commands::set_repository_pinned::set_repository_pinned,
+            commands::reorder_repositories::reorder_repositories,
             commands::git_fetch::git_fetch,
+            commands::get_remotes::get_remotes,
+            commands::add_remote::add_remote,
+            commands::rename_remote::rename_remote,
+            commands::remove_remote::remove_remote,
+            commands::set_remote_url::set_remote_url,
+            commands::prune_remote::prune_remote,
+            commands::fetch_remote::fetch_remote,
+            commands::get_worktrees::get_worktrees,
+            commands::add_worktree::add_worktree,
+            commands::remove_worktree::remove_worktree,
+            commands::lock_worktree::lock_worktree,
+            commands::unlock_worktree::unlock_worktree,
+            commands::prune_worktrees::prune_worktrees,
+            commands::register_worktree::register_worktree,
+            commands::get_submodules::get_submodules,
+            commands::init_submodules::init_submodules,
+            commands::update_submodules::update_submodules,
+            commands::sync_submodules::sync_submodules,
+            commands::deinit_submodules::deinit_submodules,
             commands::get_git_command_log::get_git_command_log,
+            commands::set_git_command_log_retention::set_git_command_log_retention,
+            commands::export_git_command_log::export_git_command_log,
             commands::get_graph::get_graph,
             commands::get_commit_files::get_commit_files,
             commands::get_file_diff::get_file_diff,
+            commands::compare_revisions::compare_revisions,
+            commands::export_patches::export_patches,
+            commands::create_patch::create_patch,
+            commands::check_patch::check_patch,
+            commands::apply_patch::apply_patch,
+            commands::get_running_git_commands::get_running_git_commands,
+            commands::cancel_git_command::cancel_git_command,
             commands::get_changed_files::get_changed_files,
+            commands::get_settings::get_settings,
+            commands::update_settings::update_settings,
+            commands::get_git_version::get_git_version,
         ])
-        .events(collect_events![GitCommandEvent]);
+        .events(collect_events![
+            GitCommandEvent,
+            GitProgressEvent,
+            SettingsChangedEvent,
+            RepositoryFetchedEvent,
+            WorkingTreeChangedEvent,
+            RefsChangedEvent,
+            HeadMovedEvent,
+            RepositoryWatchFailedEvent,
+            RepositoryStatusEvent
+        ]);
 
     // Generate ts types
     #[cfg(debug_assertions)]
@@ -69,7 +134,25 @@ fn main() {
                 // Load into app state and manage with Tauri
                 let db = Db::new(pool);
                 db.load().await?;
+                db.check_git_version().await;
+
+                // Watch the repository that was open when the app was closed. A failure is kept
+                // by the watcher, so the frontend can get it once it's loaded.
+                let watcher = RepositoryWatcher::default();
+                let open_repository = db.state.lock().await.open_repository.clone();
+                let _ = watcher.watch(app.handle(), open_repository.as_ref());
+
                 app.manage(db);
+                app.manage(GitCommandRegistry::default());
+                app.manage(MovedBlockCache::default());
+                app.manage(watcher);
+                auto_fetch::start(app.handle().clone());
+                status_check::start(app.handle().clone());
+
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    normalize_repository_paths(&app_handle).await;
+                });
 
                 Ok(())
             })
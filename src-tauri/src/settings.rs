@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri_specta::Event;
+
+use crate::structures::file_diff::DiffLayout;
+
+/// Application settings, stored in the database. Defaults match the `settings` table.
+#[derive(Serialize, Deserialize, Clone, Type, Debug)]
+pub struct Settings {
+    /// Number of logged commands of each type to keep for newly added repositories
+    pub git_command_log_retention: i64,
+    /// Number of commits to load in the graph
+    pub graph_page_size: i64,
+    /// None to disable fetching in the background
+    pub auto_fetch_interval_minutes: Option<i64>,
+    /// Fetch every added repository in the background, not just the open one
+    pub auto_fetch_all_repositories: bool,
+    pub diff_layout: DiffLayout,
+    /// Files larger than this many bytes will skip syntax highlighting and word diffs
+    pub max_highlight_size: i64,
+    /// Images larger than this many bytes will be shown as binary files instead
+    pub max_image_size: i64,
+    /// Path to the git executable, None to use `git` from the PATH
+    pub git_executable: Option<String>,
+    /// Extra environment variables set for every git command
+    pub git_environment: Vec<GitEnvironmentVariable>,
+    /// Config passed with `-c key=value` to every git command
+    pub git_config_overrides: Vec<GitConfigOverride>,
+    /// Branch names that are never offered for cleanup, `*` matches any characters
+    pub protected_branches: Vec<String>,
+    /// Maximum time queries can run for before they're killed, None for no limit
+    pub git_query_timeout_seconds: Option<i64>,
+    /// Maximum time mutations can run for before they're killed, None for no limit
+    pub git_mutation_timeout_seconds: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Type, Debug)]
+pub struct GitEnvironmentVariable {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Type, Debug)]
+pub struct GitConfigOverride {
+    /// e.g. `core.autocrlf`
+    pub key: String,
+    pub value: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            git_command_log_retention: 200,
+            graph_page_size: 500,
+            auto_fetch_interval_minutes: Some(10),
+            auto_fetch_all_repositories: false,
+            diff_layout: DiffLayout::Unified,
+            max_highlight_size: 1024 * 1024,
+            max_image_size: 10 * 1024 * 1024,
+            git_executable: None,
+            git_environment: Vec::new(),
+            git_config_overrides: Vec::new(),
+            protected_branches: vec![
+                "main".into(),
+                "master".into(),
+                "develop".into(),
+                "release/*".into(),
+            ],
+            git_query_timeout_seconds: Some(60),
+            // Mutations like fetch can legitimately take a long time on slow connections
+            git_mutation_timeout_seconds: None,
+        }
+    }
+}
+
+impl Settings {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.git_command_log_retention < 1 {
+            return Err("Log retention must be at least 1".into());
+        }
+        if self.graph_page_size < 1 {
+            return Err("Graph page size must be at least 1".into());
+        }
+        if self
+            .auto_fetch_interval_minutes
+            .is_some_and(|interval| interval < 1)
+        {
+            return Err("Auto fetch interval must be at least 1 minute".into());
+        }
+        if self.max_highlight_size < 0 || self.max_image_size < 0 {
+            return Err("File size limits can't be negative".into());
+        }
+        if self
+            .git_executable
+            .as_ref()
+            .is_some_and(|path| path.trim().is_empty())
+        {
+            return Err("Git executable path can't be empty".into());
+        }
+        if let Some(variable) = self
+            .git_environment
+            .iter()
+            .find(|variable| variable.name.is_empty() || variable.name.contains(['=', '\0']))
+        {
+            return Err(format!(
+                "\"{}\" isn't a valid environment variable name",
+                variable.name
+            ));
+        }
+        if let Some(config) = self
+            .git_config_overrides
+            .iter()
+            .find(|config| !config.key.contains('.') || config.key.contains(['=', ' ']))
+        {
+            return Err(format!(
+                "\"{}\" isn't a valid git config key, it should look like section.name",
+                config.key
+            ));
+        }
+        if self
+            .protected_branches
+            .iter()
+            .any(|pattern| pattern.trim().is_empty())
+        {
+            return Err("Protected branch names can't be empty".into());
+        }
+        if [
+            self.git_query_timeout_seconds,
+            self.git_mutation_timeout_seconds,
+        ]
+        .iter()
+        .any(|timeout| timeout.is_some_and(|timeout| timeout < 1))
+        {
+            return Err("Command timeouts must be at least 1 second".into());
+        }
+        Ok(())
+    }
+}
+
+/// Emitted when the settings are updated
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct SettingsChangedEvent(pub Settings);
+
+#[cfg(test)]
+mod test {
+    use super::{GitConfigOverride, GitEnvironmentVariable, Settings};
+
+    fn is_valid(change: impl FnOnce(&mut Settings)) -> bool {
+        let mut settings = Settings::default();
+        change(&mut settings);
+        settings.validate().is_ok()
+    }
+
+    #[test]
+    fn validates_settings() {
+        assert!(is_valid(|_| {}));
+
+        assert!(is_valid(|s| s.git_command_log_retention = 1));
+        assert!(!is_valid(|s| s.git_command_log_retention = 0));
+        assert!(!is_valid(|s| s.graph_page_size = 0));
+        assert!(is_valid(|s| s.auto_fetch_interval_minutes = None));
+        assert!(!is_valid(|s| s.auto_fetch_interval_minutes = Some(0)));
+        assert!(!is_valid(|s| s.max_highlight_size = -1));
+        assert!(!is_valid(|s| s.max_image_size = -1));
+        assert!(!is_valid(|s| s.git_executable = Some(" ".into())));
+        assert!(is_valid(|s| s.git_query_timeout_seconds = None));
+        assert!(!is_valid(|s| s.git_query_timeout_seconds = Some(0)));
+        assert!(!is_valid(|s| s.git_mutation_timeout_seconds = Some(-5)));
+        assert!(!is_valid(|s| s.protected_branches.push("".into())));
+    }
+
+    #[test]
+    fn validates_git_environment_and_config() {
+        let variable = |name: &str| GitEnvironmentVariable {
+            name: name.into(),
+            value: "value".into(),
+        };
+        assert!(is_valid(|s| s.git_environment = vec![variable("GIT_TRACE")]));
+        assert!(!is_valid(|s| s.git_environment = vec![variable("")]));
+        assert!(!is_valid(|s| s.git_environment = vec![variable("A=B")]));
+
+        let config = |key: &str| GitConfigOverride {
+            key: key.into(),
+            value: "true".into(),
+        };
+        assert!(is_valid(
+            |s| s.git_config_overrides = vec![config("core.autocrlf")]
+        ));
+        assert!(!is_valid(
+            |s| s.git_config_overrides = vec![config("autocrlf")]
+        ));
+        assert!(!is_valid(
+            |s| s.git_config_overrides = vec![config("core.a b")]
+        ));
+        assert!(!is_valid(
+            |s| s.git_config_overrides = vec![config("core.a=b")]
+        ));
+    }
+}
@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::{
+    cli::GitCommand,
+    commands::{CommandError, CommandResult},
+    db::{Db, GitCommandType, Repository},
+    structures::repository_status::RepositoryStatus,
+};
+
+/// How often to check the status of every repository
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Emitted when a repository is updated in the background, e.g. its changes or ahead/behind counts
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct RepositoryStatusEvent(pub Repository);
+
+/// Start checking every repository for changes in the background
+pub fn start(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            check_repositories(&app_handle).await;
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+async fn check_repositories(app_handle: &tauri::AppHandle) {
+    let db = app_handle.state::<Db>();
+    let Ok(repositories) = db.get_repositories().await else {
+        return;
+    };
+
+    for repository in repositories {
+        // e.g. the repository was moved or deleted, which is in the command log
+        let Ok(status) = check(app_handle, &repository).await else {
+            continue;
+        };
+        let Ok(updated) = db.set_repository_status(repository.id, &status).await else {
+            continue;
+        };
+
+        if (repository.has_changes, repository.ahead, repository.behind)
+            != (updated.has_changes, updated.ahead, updated.behind)
+        {
+            RepositoryStatusEvent(updated)
+                .emit(app_handle)
+                .expect("Failed to emit event");
+        }
+    }
+}
+
+async fn check(
+    app_handle: &tauri::AppHandle,
+    repository: &Repository,
+) -> CommandResult<RepositoryStatus> {
+    GitCommand::new("status")
+        .arg("--porcelain=v2")
+        .arg("--branch")
+        .repository(repository)
+        // Every repository is checked each minute, which would fill up its command log
+        .skip_log()
+        // Don't take the index lock, which could make the user's own commands fail
+        .env("GIT_OPTIONAL_LOCKS", "0")
+        .run(app_handle, GitCommandType::Query)
+        .await?
+        .parse()
+        .map_err(CommandError::Parse)
+}
@@ -13,6 +13,8 @@ pub struct LocalBranch {
     pub upstream_name: Vec<String>,
     pub upstream_track: UpstreamTrack,
     pub hash: GitHash,
+    /// Path of the worktree the branch is checked out in, which is the open one when `head`
+    pub worktree_path: Option<String>,
 }
 
 #[derive(Debug, Serialize, Type)]
@@ -51,6 +53,10 @@ impl FromStr for LocalBranch {
                 .next()
                 .ok_or("Failed to get local branch hash")?
                 .parse()?,
+            worktree_path: parts
+                .next()
+                .ok_or("Failed to get local branch worktree path")
+                .map(|path| Some(path.to_owned()).filter(|path| !path.is_empty()))?,
         })
     }
 }
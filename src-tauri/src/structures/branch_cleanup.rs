@@ -0,0 +1,215 @@
+use std::{collections::HashSet, str::FromStr};
+
+use chrono::{DateTime, NaiveDateTime};
+use serde::Serialize;
+use specta::Type;
+
+use super::{hash::GitHash, upstream_track::UpstreamTrack};
+
+/// Fields to request from `git for-each-ref --format`, in the order they're parsed
+pub const CLEANUP_BRANCH_FIELDS: &[&str] = &[
+    "(refname:lstrip=2)",
+    "(worktreepath)",
+    "(upstream:track,nobracket)",
+    "(objectname)",
+    "(committerdate:unix)",
+    "(authorname)",
+    "(subject)",
+];
+
+#[derive(Debug, Serialize, Type, PartialEq)]
+pub enum BranchCleanupReason {
+    /// Fully merged into the chosen base
+    Merged,
+    /// The upstream branch was deleted from the remote
+    UpstreamGone,
+}
+
+#[derive(Debug, Serialize, Type, PartialEq)]
+pub struct BranchCleanupCandidate {
+    /// Full branch name, e.g. `feat/implement-stuff`
+    pub name: String,
+    pub reasons: Vec<BranchCleanupReason>,
+    pub hash: GitHash,
+    pub last_commit_author: String,
+    pub last_commit_date: NaiveDateTime,
+    pub last_commit_message: String,
+}
+
+/// A local branch as listed with [`CLEANUP_BRANCH_FIELDS`]
+#[derive(Debug)]
+pub struct CleanupBranch {
+    pub name: String,
+    /// Checked out in this or another worktree, so it can't be deleted
+    pub checked_out: bool,
+    pub upstream_track: UpstreamTrack,
+    pub hash: GitHash,
+    pub author: String,
+    pub date: NaiveDateTime,
+    pub message: String,
+}
+
+impl FromStr for CleanupBranch {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('\x00');
+        Ok(Self {
+            name: parts.next().ok_or("Failed to get branch name")?.into(),
+            checked_out: !parts
+                .next()
+                .ok_or("Failed to get branch worktree path")?
+                .is_empty(),
+            upstream_track: parts
+                .next()
+                .ok_or("Failed to get branch upstream track status")?
+                .parse()?,
+            hash: parts.next().ok_or("Failed to get branch hash")?.parse()?,
+            date: DateTime::from_timestamp(
+                parts
+                    .next()
+                    .ok_or("Failed to get branch commit date")?
+                    .parse()
+                    .map_err(|err| format!("Failed to parse date: {}", err))?,
+                0,
+            )
+            .ok_or("Failed to convert date to timestamp")?
+            .naive_utc(),
+            author: parts
+                .next()
+                .ok_or("Failed to get branch commit author")?
+                .into(),
+            message: parts
+                .next()
+                .ok_or("Failed to get branch commit message")?
+                .into(),
+        })
+    }
+}
+
+impl CleanupBranch {
+    /// Whether the branch can never be deleted by a cleanup
+    pub fn is_protected(&self, protected_patterns: &[String]) -> bool {
+        self.checked_out
+            || protected_patterns
+                .iter()
+                .any(|pattern| matches_pattern(pattern, &self.name))
+    }
+}
+
+impl BranchCleanupCandidate {
+    /// Pick the branches worth cleaning up from all local branches.
+    /// `merged` holds the names of the branches merged into `base`.
+    pub fn from_branches(
+        branches: Vec<CleanupBranch>,
+        merged: &HashSet<&str>,
+        base: &str,
+        protected_patterns: &[String],
+    ) -> Vec<Self> {
+        branches
+            .into_iter()
+            .filter(|branch| branch.name != base && !branch.is_protected(protected_patterns))
+            .filter_map(|branch| {
+                let mut reasons = Vec::new();
+                if merged.contains(branch.name.as_str()) {
+                    reasons.push(BranchCleanupReason::Merged);
+                }
+                if branch.upstream_track.is_gone() {
+                    reasons.push(BranchCleanupReason::UpstreamGone);
+                }
+                (!reasons.is_empty()).then_some(Self {
+                    name: branch.name,
+                    reasons,
+                    hash: branch.hash,
+                    last_commit_author: branch.author,
+                    last_commit_date: branch.date,
+                    last_commit_message: branch.message,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Match a branch name against a pattern where `*` matches any characters, including `/`
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts = parts.peekable();
+    while let Some(part) = parts.next() {
+        if parts.peek().is_none() {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.is_empty()
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::{matches_pattern, BranchCleanupCandidate, BranchCleanupReason, CleanupBranch};
+
+    #[test]
+    fn matches_protected_patterns() {
+        assert!(matches_pattern("main", "main"));
+        assert!(!matches_pattern("main", "main-old"));
+        assert!(matches_pattern("release/*", "release/1.0"));
+        assert!(matches_pattern("release/*", "release/1.x/hotfix"));
+        assert!(!matches_pattern("release/*", "releases/1.0"));
+        assert!(matches_pattern("*-stable", "v2-stable"));
+        assert!(matches_pattern("feat/*/keep", "feat/a/b/keep"));
+        assert!(!matches_pattern("feat/*/keep", "feat/a/keeper"));
+        assert!(matches_pattern("*", "anything"));
+    }
+
+    #[test]
+    fn picks_merged_and_gone_branches() {
+        let hash = "0123456789abcdef0123456789abcdef01234567";
+        let output = [
+            format!("main\0/repo\0\0{hash}\01700000000\0Ann\0Initial commit"),
+            format!("feat/merged\0\0\0{hash}\01700000000\0Ann\0Merged work"),
+            format!("feat/gone\0\0gone\0{hash}\01700000100\0Bob\0Abandoned"),
+            format!("feat/both\0\0gone\0{hash}\01700000200\0Bob\0Done"),
+            format!("feat/active\0\0ahead 2\0{hash}\01700000300\0Bob\0Still going"),
+            format!("release/1.0\0\0gone\0{hash}\01700000400\0Ann\0Release"),
+            format!("wip\0/other-worktree\0gone\0{hash}\01700000500\0Ann\0Checked out"),
+        ];
+        let branches = output
+            .iter()
+            .map(|line| line.parse::<CleanupBranch>().unwrap())
+            .collect();
+        let merged = HashSet::from(["main", "feat/merged", "feat/both", "release/1.0"]);
+        let candidates = BranchCleanupCandidate::from_branches(
+            branches,
+            &merged,
+            "main",
+            &["main".into(), "release/*".into()],
+        );
+
+        assert_eq!(
+            candidates
+                .iter()
+                .map(|candidate| (candidate.name.as_str(), &candidate.reasons))
+                .collect::<Vec<_>>(),
+            vec![
+                ("feat/merged", &vec![BranchCleanupReason::Merged]),
+                ("feat/gone", &vec![BranchCleanupReason::UpstreamGone]),
+                (
+                    "feat/both",
+                    &vec![
+                        BranchCleanupReason::Merged,
+                        BranchCleanupReason::UpstreamGone
+                    ]
+                ),
+            ]
+        );
+        assert_eq!(candidates[1].last_commit_author, "Bob");
+        assert_eq!(candidates[1].last_commit_message, "Abandoned");
+    }
+}
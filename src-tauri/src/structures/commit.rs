@@ -7,6 +7,20 @@ use specta::Type;
 
 use super::hash::GitHash;
 
+/// Fields to request from `git log --format`, in the order they're parsed
+pub const LOG_FIELDS: &[&str] = &[
+    "H",  // Commit hash
+    "P",  // Parent hashes
+    "an", // Author name
+    "ae", // Author email
+    "at", // Author date
+    "cn", // Committer name
+    "ce", // Committer email
+    "ct", // Committer date
+    "s",  // Commit message
+    "b",  // Commit description
+];
+
 #[derive(Debug, Serialize, Type)]
 pub struct CommitUser {
     pub name: String,
@@ -53,6 +67,18 @@ impl FromStr for Commit {
     }
 }
 
+impl Commit {
+    /// Parse the output of `git log` with [`LOG_FIELDS`] separated by NULs and each commit terminated by `\x01`
+    pub fn from_log(log: &str) -> Result<Vec<Self>, String> {
+        // TODO: kinda sus way of delimiting commits, investigate a cleaner solution
+        let log = log.trim().trim_end_matches('\x01');
+        if log.is_empty() {
+            return Ok(Vec::new());
+        }
+        log.split("\x01\n").map(|line| line.parse()).collect()
+    }
+}
+
 fn parse_user(name: &str, email: &str, date: &str) -> Result<CommitUser, String> {
     Ok(CommitUser {
         name: name.into(),
@@ -1,9 +1,14 @@
-use std::str::FromStr;
+use std::{collections::HashMap, str::FromStr};
 
 use serde::Serialize;
 use specta::Type;
 
-use super::{file_status::FileStatus, hash::GitHash};
+use super::{
+    file_stats::{DiffStats, FileStats},
+    file_status::FileStatus,
+    hash::GitHash,
+    submodule::{SubmoduleChange, SUBMODULE_MODE},
+};
 
 #[derive(Debug, Serialize, Type)]
 pub struct File {
@@ -17,14 +22,64 @@ pub struct File {
     pub src_path: String,
     /// Optional destination path if status is copied or renamed
     pub dst_path: Option<String>,
+    /// None if line counts aren't available, e.g. for untracked files or combined diffs
+    pub stats: Option<FileStats>,
+    /// Some if the file is a submodule, in which case the hashes are the commits it moved between
+    pub submodule: Option<SubmoduleChange>,
+}
+
+#[derive(Debug, Serialize, Type)]
+pub struct CommitFiles {
+    pub files: Vec<File>,
+    /// None if line counts aren't available, e.g. for combined diffs
+    pub stats: Option<DiffStats>,
+}
+
+impl File {
+    /// Parse the output of a diff with `--raw -z`
+    pub fn from_raw(raw: &str) -> Result<Vec<Self>, String> {
+        // Combined diffs can hide every file in a merge without conflicts
+        if raw.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        raw.strip_prefix(':')
+            .ok_or("Failed to strip files prefix")?
+            .strip_suffix('\x00')
+            .ok_or("Failed to strip files suffix")?
+            .split("\x00:")
+            .map(|line| line.parse())
+            .collect()
+    }
+
+    /// Attach stats from [`FileStats::from_numstat`] to each file
+    pub fn with_stats(mut files: Vec<Self>, stats: &HashMap<String, FileStats>) -> Vec<Self> {
+        for file in &mut files {
+            file.stats = stats
+                .get(file.dst_path.as_ref().unwrap_or(&file.src_path))
+                .copied();
+        }
+        files
+    }
 }
 
 impl FromStr for File {
     type Err = String;
+    /// Parses a line of `--raw -z` output without the leading colon. Combined diffs of merge
+    /// commits have an extra colon for each additional parent, along with a mode, hash and
+    /// status for each parent.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split(' ').skip(2);
+        let parents = s.chars().take_while(|c| *c == ':').count() + 1;
+        // Paths can contain spaces, so keep the status and paths together as the last part
+        let mut parts = s.trim_start_matches(':').splitn(2 * (parents + 1) + 1, ' ');
 
+        // A mode for each parent and the result, any of which can be a submodule
+        let modes: Vec<&str> = parts.by_ref().take(parents + 1).collect();
+        let is_submodule = modes.contains(&SUBMODULE_MODE);
+
+        // Combined diffs are shown against the first parent
         let src_hash = GitHash::from_optional(parts.next().ok_or("Failed to get file src hash")?)?;
+        let mut parts = parts.skip(parents - 1);
         let dst_hash = GitHash::from_optional(parts.next().ok_or("Failed to get file dst hash")?)?;
 
         let (status, paths) = parts
@@ -33,7 +88,25 @@ impl FromStr for File {
             .split_once('\x00')
             .ok_or("Failed to split file status and paths")?;
 
-        let (status, score) = status.split_at(1);
+        let (status, score) = if parents > 1 {
+            // Combined diffs have a status for each parent and no score
+            let mut statuses = status.chars();
+            let first = statuses.next().ok_or("Failed to get file status")?;
+            (
+                if statuses.all(|status| status == first) {
+                    first
+                } else {
+                    'M'
+                },
+                "",
+            )
+        } else {
+            let (status, score) = status.split_at(1);
+            (
+                status.chars().next().ok_or("Failed to get file status")?,
+                score,
+            )
+        };
 
         let (src_path, dst_path) = match paths.split_once('\x00') {
             Some((src_path, dst_path)) => (src_path.into(), Some(dst_path.into())),
@@ -41,13 +114,13 @@ impl FromStr for File {
         };
 
         Ok(Self {
+            submodule: is_submodule.then(|| SubmoduleChange {
+                commit_changed: src_hash != dst_hash,
+                ..Default::default()
+            }),
             src_hash,
             dst_hash,
-            status: status
-                .chars()
-                .next()
-                .ok_or("Failed to get file status")?
-                .try_into()?,
+            status: status.try_into()?,
             score: if score.is_empty() {
                 None
             } else {
@@ -59,6 +132,58 @@ impl FromStr for File {
             },
             src_path,
             dst_path,
+            stats: None,
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::structures::{file_status::FileStatus, hash::GitHash, submodule::SubmoduleChange};
+
+    use super::File;
+
+    #[test]
+    fn parses_a_combined_file() {
+        let file: File = ":100644 100644 100644 1111111111111111111111111111111111111111 2222222222222222222222222222222222222222 3333333333333333333333333333333333333333 MM\x00src/main.rs"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            file.src_hash,
+            Some(GitHash("1111111111111111111111111111111111111111".into()))
+        );
+        assert_eq!(
+            file.dst_hash,
+            Some(GitHash("3333333333333333333333333333333333333333".into()))
+        );
+        assert!(matches!(file.status, FileStatus::Modified));
+        assert_eq!(file.score, None);
+        assert_eq!(file.src_path, "src/main.rs");
+        assert_eq!(file.dst_path, None);
+        assert_eq!(file.submodule, None);
+    }
+
+    #[test]
+    fn parses_a_moved_submodule() {
+        let file: File = "160000 160000 d6b791fb7420c481a7fda55dae90634541d3f7fe f52aec8e19baa83f962a0c9e404238a002f2120f M\x00libs/my lib"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            file.src_hash,
+            Some(GitHash("d6b791fb7420c481a7fda55dae90634541d3f7fe".into()))
+        );
+        assert_eq!(
+            file.dst_hash,
+            Some(GitHash("f52aec8e19baa83f962a0c9e404238a002f2120f".into()))
+        );
+        assert_eq!(file.src_path, "libs/my lib");
+        assert_eq!(
+            file.submodule,
+            Some(SubmoduleChange {
+                commit_changed: true,
+                modified_content: false,
+                untracked_content: false,
+            })
+        );
+    }
+}
@@ -1,6 +1,6 @@
-use std::{ops::Range, time::Duration};
+use std::{cmp, ops::Range, time::Duration};
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use specta::Type;
 
 use crate::{
@@ -8,7 +8,12 @@ use crate::{
     utils::highlight::{HighlightLanguage, Highlights, SyntaxHighlighter},
 };
 
-use super::{diff_status::DiffStatus, file_diff_meta::FileDiffMeta};
+use super::{
+    diff_status::DiffStatus,
+    file_diff_meta::{CombinedFileDiffMeta, FileDiffMeta},
+    hash::GitHash,
+    moved_block::{MovedBlock, MovedLine},
+};
 
 #[derive(Debug, Serialize, Type, Clone, Eq, PartialEq)]
 pub struct Fragment {
@@ -25,6 +30,8 @@ pub struct LineDiff {
     pub src_line_number: Option<usize>,
     /// None if status is Removed
     pub dst_line_number: Option<usize>,
+    /// Set if this line is part of a block that was moved
+    pub moved: Option<MovedLine>,
 }
 
 #[derive(Debug, Serialize, Type)]
@@ -35,27 +42,162 @@ pub struct DiffHunk {
 }
 
 #[derive(Debug, Serialize, Type)]
-pub struct FileDiff(pub Vec<DiffHunk>);
+pub struct SplitDiffRow {
+    /// None if this is a filler row alongside an added line
+    pub src: Option<LineDiff>,
+    /// None if this is a filler row alongside a removed line
+    pub dst: Option<LineDiff>,
+}
+
+#[derive(Debug, Serialize, Type)]
+pub struct SplitDiffHunk {
+    /// Raw header text, or None if the whole file was requested
+    pub header: Option<String>,
+    pub rows: Vec<SplitDiffRow>,
+}
+
+#[derive(Debug, Serialize, Type)]
+pub struct CombinedLineDiff {
+    pub fragments: Vec<Fragment>,
+    /// Status of the line compared to each parent
+    pub statuses: Vec<DiffStatus>,
+    /// Line number in each parent, None if the line isn't in that parent
+    pub src_line_numbers: Vec<Option<usize>>,
+    /// None if the line was removed from the result
+    pub dst_line_number: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Type)]
+pub struct CombinedDiffHunk {
+    /// Raw header text
+    pub header: String,
+    pub lines: Vec<CombinedLineDiff>,
+}
+
+/// Which changes to show for a merge commit. Has no effect on commits with a single parent.
+#[derive(Debug, Deserialize, Type, Clone, Copy)]
+pub enum MergeDiffMode {
+    /// Changes compared to a single parent, starting from 1
+    Parent(usize),
+    /// Changes compared to all parents at once, only for files that differ from every parent (`-c`)
+    Combined,
+    /// Like combined, but also hides hunks that match one of the parents (`--cc`)
+    DenseCombined,
+}
+
+/// The revisions to diff a file between
+#[derive(Debug, Deserialize, Type, Clone)]
+pub enum DiffRevisions {
+    /// Changes made in a single commit
+    Commit {
+        hash: GitHash,
+        merge_diff_mode: MergeDiffMode,
+    },
+    /// Changes between two commits, e.g. from a revision comparison
+    Range {
+        src_hash: GitHash,
+        dst_hash: GitHash,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Type, Clone, Copy, PartialEq, Eq)]
+#[repr(i64)]
+pub enum DiffLayout {
+    /// Removed and added lines one after the other
+    Unified,
+    /// Removed and added lines side by side
+    Split,
+}
+
+impl TryFrom<i64> for DiffLayout {
+    type Error = String;
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Unified),
+            1 => Ok(Self::Split),
+            _ => Err(format!("Not a valid diff layout: {value}")),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Type)]
+pub struct BinaryFile {
+    /// Size in bytes
+    pub size: usize,
+    pub mime_type: String,
+}
+
+#[derive(Debug, Serialize, Type)]
+pub struct ImageFile {
+    /// Size in bytes
+    pub size: usize,
+    pub mime_type: String,
+    /// Contents of the image encoded as base64
+    pub base64: String,
+}
+
+#[derive(Debug, Serialize, Type)]
+pub enum FileDiff {
+    Text(Vec<DiffHunk>),
+    /// A text diff with removed and added lines aligned side by side
+    Split(Vec<SplitDiffHunk>),
+    /// A text diff of a merge commit against all of its parents
+    Combined(Vec<CombinedDiffHunk>),
+    /// Git considers the file binary, so there is no text diff
+    Binary {
+        /// None if the file was added
+        src: Option<BinaryFile>,
+        /// None if the file was deleted
+        dst: Option<BinaryFile>,
+    },
+    /// A binary file that can be displayed as an image
+    Image {
+        /// None if the file was added
+        src: Option<ImageFile>,
+        /// None if the file was deleted
+        dst: Option<ImageFile>,
+    },
+    /// A submodule that moved from one commit to another
+    Submodule {
+        /// None if the submodule was added
+        src: Option<GitHash>,
+        /// None if the submodule was removed
+        dst: Option<GitHash>,
+    },
+}
 
 impl FileDiff {
     pub fn from(
         meta: FileDiffMeta,
         src_file: Option<String>,
         dst_file: Option<String>,
+        max_highlight_size: usize,
     ) -> Result<Self, String> {
         let src_lines: Option<Vec<&str>> = src_file.as_ref().map(|file| file.lines().collect());
         let dst_lines: Option<Vec<&str>> = dst_file.as_ref().map(|file| file.lines().collect());
 
+        let is_large = [&src_file, &dst_file]
+            .into_iter()
+            .flatten()
+            .any(|file| file.len() > max_highlight_size);
+
         let mut highlighter = SyntaxHighlighter::new();
+        let mut highlight = |path: &str, file: &String| {
+            if is_large {
+                Highlights(Vec::new())
+            } else {
+                highlighter.highlight(HighlightLanguage::from_path(path), file)
+            }
+        };
 
         let src_highlight = src_file
             .as_ref()
-            .map(|file| highlighter.highlight(HighlightLanguage::from_path(&meta.src_path), file));
+            .map(|file| highlight(&meta.src_path, file));
         let dst_highlight = dst_file
             .as_ref()
-            .map(|file| highlighter.highlight(HighlightLanguage::from_path(&meta.dst_path), file));
+            .map(|file| highlight(&meta.dst_path, file));
 
-        Ok(Self(
+        Ok(Self::Text(
             meta.hunks
                 .into_iter()
                 .map(|hunk| {
@@ -80,49 +222,55 @@ impl FileDiff {
                                             status: DiffStatus::Unmodified,
                                             src_line_number: Some(src_line),
                                             dst_line_number: Some(dst_line),
+                                            moved: None,
                                         })
                                         .collect::<Vec<_>>()
                                 }
                                 (HunkSection::Added(line_numbers), Some(_) | None, Some(lines)) => {
-                                    line_numbers
-                                        .into_iter()
-                                        .map(|line_number| LineDiff {
-                                            fragments: Fragment::from_highlighted(
-                                                line_number_range(
-                                                    line_number,
-                                                    dst_file.as_ref().unwrap(),
-                                                ),
-                                                lines[line_number - 1].into(),
-                                                DiffStatus::Unmodified,
-                                                dst_highlight.as_ref().unwrap(),
-                                            ),
-                                            status: DiffStatus::Added,
-                                            src_line_number: None,
-                                            dst_line_number: Some(line_number),
-                                        })
-                                        .collect::<Vec<_>>()
+                                    whole_line_diffs(
+                                        line_numbers,
+                                        lines,
+                                        dst_file.as_ref().unwrap(),
+                                        dst_highlight.as_ref().unwrap(),
+                                        DiffStatus::Added,
+                                    )
                                 }
                                 (
                                     HunkSection::Removed(line_numbers),
                                     Some(lines),
                                     Some(_) | None,
-                                ) => line_numbers
-                                    .into_iter()
-                                    .map(|line_number| LineDiff {
-                                        fragments: Fragment::from_highlighted(
-                                            line_number_range(
-                                                line_number,
-                                                src_file.as_ref().unwrap(),
-                                            ),
-                                            lines[line_number - 1].into(),
-                                            DiffStatus::Unmodified,
-                                            src_highlight.as_ref().unwrap(),
-                                        ),
-                                        status: DiffStatus::Removed,
-                                        src_line_number: Some(line_number),
-                                        dst_line_number: None,
-                                    })
-                                    .collect::<Vec<_>>(),
+                                ) => whole_line_diffs(
+                                    line_numbers,
+                                    lines,
+                                    src_file.as_ref().unwrap(),
+                                    src_highlight.as_ref().unwrap(),
+                                    DiffStatus::Removed,
+                                ),
+                                // Skip calculating a word diff for large files
+                                (
+                                    HunkSection::RemovedAdded(
+                                        removed_line_numbers,
+                                        added_line_numbers,
+                                    ),
+                                    Some(removed_lines),
+                                    Some(added_lines),
+                                ) if is_large => [
+                                    whole_line_diffs(
+                                        removed_line_numbers,
+                                        removed_lines,
+                                        src_file.as_ref().unwrap(),
+                                        src_highlight.as_ref().unwrap(),
+                                        DiffStatus::Removed,
+                                    ),
+                                    whole_line_diffs(
+                                        added_line_numbers,
+                                        added_lines,
+                                        dst_file.as_ref().unwrap(),
+                                        dst_highlight.as_ref().unwrap(),
+                                        DiffStatus::Added,
+                                    ),
+                                ]
+                                .concat(),
                                 (
                                     HunkSection::RemovedAdded(
                                         removed_line_numbers,
@@ -229,6 +377,7 @@ impl FileDiff {
                                         status: DiffStatus::Removed,
                                         src_line_number: Some(removed_line_numbers[i]),
                                         dst_line_number: None,
+                                        moved: None,
                                     })
                                     .collect();
 
@@ -244,6 +393,7 @@ impl FileDiff {
                                         status: DiffStatus::Added,
                                         src_line_number: None,
                                         dst_line_number: Some(added_line_numbers[i]),
+                                        moved: None,
                                     })
                                     .collect();
 
@@ -264,6 +414,176 @@ impl FileDiff {
     }
 }
 
+impl FileDiff {
+    /// Create a combined diff, highlighting removed lines from the first parent they appear in
+    pub fn from_combined(
+        meta: CombinedFileDiffMeta,
+        src_files: Vec<Option<String>>,
+        dst_file: Option<String>,
+        max_highlight_size: usize,
+    ) -> Self {
+        let is_large = src_files
+            .iter()
+            .chain([&dst_file])
+            .flatten()
+            .any(|file| file.len() > max_highlight_size);
+
+        let mut highlighter = SyntaxHighlighter::new();
+        let mut highlight = |file: &String| {
+            if is_large {
+                Highlights(Vec::new())
+            } else {
+                highlighter.highlight(HighlightLanguage::from_path(&meta.path), file)
+            }
+        };
+
+        let src_highlights: Vec<_> = src_files
+            .iter()
+            .map(|file| file.as_ref().map(&mut highlight))
+            .collect();
+        let dst_highlight = dst_file.as_ref().map(&mut highlight);
+
+        Self::Combined(
+            meta.hunks
+                .into_iter()
+                .map(|hunk| CombinedDiffHunk {
+                    header: hunk.header,
+                    lines: hunk
+                        .lines
+                        .into_iter()
+                        .map(|line| {
+                            let (line_number, file, highlights) = match line.dst_line_number {
+                                Some(line_number) => (line_number, &dst_file, &dst_highlight),
+                                None => line
+                                    .src_line_numbers
+                                    .iter()
+                                    .enumerate()
+                                    .find_map(|(i, line_number)| {
+                                        line_number.map(|line_number| {
+                                            (line_number, &src_files[i], &src_highlights[i])
+                                        })
+                                    })
+                                    .expect("Combined diff line isn't in any file"),
+                            };
+                            let (file, highlights) = (
+                                file.as_ref().expect("Combined diff file is missing"),
+                                highlights.as_ref().unwrap(),
+                            );
+                            let range = line_number_range(line_number, file);
+
+                            CombinedLineDiff {
+                                fragments: Fragment::from_highlighted(
+                                    range.clone(),
+                                    file[range].into(),
+                                    DiffStatus::Unmodified,
+                                    highlights,
+                                ),
+                                statuses: line.statuses,
+                                src_line_numbers: line.src_line_numbers,
+                                dst_line_number: line.dst_line_number,
+                            }
+                        })
+                        .collect(),
+                })
+                .collect(),
+        )
+    }
+
+    /// Annotate removed and added lines that are part of a moved block with the other side of the move
+    pub fn mark_moved_lines(&mut self, blocks: &[MovedBlock], src_path: &str, dst_path: &str) {
+        let Self::Text(hunks) = self else {
+            return;
+        };
+
+        for line in hunks.iter_mut().flat_map(|hunk| hunk.lines.iter_mut()) {
+            line.moved = match (&line.status, line.src_line_number, line.dst_line_number) {
+                (DiffStatus::Removed, Some(line_number), _) => blocks
+                    .iter()
+                    .enumerate()
+                    .find(|(_, block)| {
+                        block.src_path == src_path && block.src_lines.contains(&line_number)
+                    })
+                    .map(|(i, block)| MovedLine {
+                        block: i,
+                        path: block.dst_path.clone(),
+                        start_line_number: block.dst_lines.start,
+                        end_line_number: block.dst_lines.end,
+                    }),
+                (DiffStatus::Added, _, Some(line_number)) => blocks
+                    .iter()
+                    .enumerate()
+                    .find(|(_, block)| {
+                        block.dst_path == dst_path && block.dst_lines.contains(&line_number)
+                    })
+                    .map(|(i, block)| MovedLine {
+                        block: i,
+                        path: block.src_path.clone(),
+                        start_line_number: block.src_lines.start,
+                        end_line_number: block.src_lines.end,
+                    }),
+                _ => None,
+            };
+        }
+    }
+
+    /// Convert a text diff into the split layout, leaving other diffs untouched
+    pub fn into_split(self) -> Self {
+        match self {
+            Self::Text(hunks) => Self::Split(hunks.into_iter().map(SplitDiffHunk::from).collect()),
+            diff => diff,
+        }
+    }
+}
+
+impl From<DiffHunk> for SplitDiffHunk {
+    fn from(hunk: DiffHunk) -> Self {
+        let mut rows = Vec::new();
+        let mut removed = Vec::new();
+        let mut added = Vec::new();
+
+        for line in hunk.lines {
+            match line.status {
+                DiffStatus::Removed => {
+                    // Removed lines after added lines start a new section
+                    if !added.is_empty() {
+                        align_lines(&mut rows, &mut removed, &mut added);
+                    }
+                    removed.push(line);
+                }
+                DiffStatus::Added => added.push(line),
+                DiffStatus::Unmodified => {
+                    align_lines(&mut rows, &mut removed, &mut added);
+                    rows.push(SplitDiffRow {
+                        src: Some(line.clone()),
+                        dst: Some(line),
+                    });
+                }
+            }
+        }
+        align_lines(&mut rows, &mut removed, &mut added);
+
+        Self {
+            header: hunk.header,
+            rows,
+        }
+    }
+}
+
+/// Pair up removed and added lines into rows, with filler rows if there are more of one than the other
+fn align_lines(
+    rows: &mut Vec<SplitDiffRow>,
+    removed: &mut Vec<LineDiff>,
+    added: &mut Vec<LineDiff>,
+) {
+    let len = cmp::max(removed.len(), added.len());
+    let mut removed = removed.drain(..);
+    let mut added = added.drain(..);
+    rows.extend((0..len).map(|_| SplitDiffRow {
+        src: removed.next(),
+        dst: added.next(),
+    }));
+}
+
 impl Fragment {
     /// Takes a string with a status and applies highlights to it.
     ///
@@ -294,6 +614,31 @@ impl Fragment {
     }
 }
 
+/// Create a line diff for each line number, without calculating a word diff
+fn whole_line_diffs(
+    line_numbers: Vec<usize>,
+    lines: &[&str],
+    file: &str,
+    highlights: &Highlights,
+    status: DiffStatus,
+) -> Vec<LineDiff> {
+    line_numbers
+        .into_iter()
+        .map(|line_number| LineDiff {
+            fragments: Fragment::from_highlighted(
+                line_number_range(line_number, file),
+                lines[line_number - 1].into(),
+                DiffStatus::Unmodified,
+                highlights,
+            ),
+            src_line_number: (status == DiffStatus::Removed).then_some(line_number),
+            dst_line_number: (status == DiffStatus::Added).then_some(line_number),
+            status: status.clone(),
+            moved: None,
+        })
+        .collect()
+}
+
 /// Take fragments where some may be terminated by a newline, and split on that.
 /// Removes all newlines.
 fn split_fragments_into_lines(fragments: Vec<&Fragment>) -> Vec<Vec<Fragment>> {
@@ -311,14 +656,14 @@ fn split_fragments_into_lines(fragments: Vec<&Fragment>) -> Vec<Vec<Fragment>> {
     lines
 }
 
-/// Get the start..end range of a single line in a file
+/// Get the start..end byte range of a single line in a file
 fn line_number_range(line_number: usize, file: &str) -> Range<usize> {
     let mut lines = file.split_inclusive('\n');
     let start = lines
         .clone()
         .take(line_number - 1)
-        .flat_map(|s| s.chars())
-        .count();
+        .map(|s| s.len())
+        .sum::<usize>();
     let end = lines
         .nth(line_number - 1)
         .unwrap()
@@ -358,7 +703,19 @@ fn tokenize_code(s: &str) -> Vec<&str> {
 mod test {
     use crate::structures::{diff_status::DiffStatus, file_diff::line_number_range};
 
-    use super::{split_fragments_into_lines, Fragment, Highlights};
+    use super::{
+        split_fragments_into_lines, DiffHunk, DiffLayout, Fragment, Highlights, LineDiff,
+        SplitDiffHunk,
+    };
+
+    #[test]
+    fn converts_stored_diff_layouts() {
+        for layout in [DiffLayout::Unified, DiffLayout::Split] {
+            assert_eq!(DiffLayout::try_from(layout as i64), Ok(layout));
+        }
+        assert!(DiffLayout::try_from(2).is_err());
+        assert!(DiffLayout::try_from(-1).is_err());
+    }
 
     #[test]
     fn calculates_line_number_range() {
@@ -375,6 +732,14 @@ Fourth and final line";
         );
     }
 
+    #[test]
+    fn calculates_line_number_range_in_bytes() {
+        let file = "Prénom: José\nÉmoji: 🦀\nLast line";
+        assert_eq!(file.get(line_number_range(1, file)), Some("Prénom: José"));
+        assert_eq!(file.get(line_number_range(2, file)), Some("Émoji: 🦀"));
+        assert_eq!(file.get(line_number_range(3, file)), Some("Last line"));
+    }
+
     #[test]
     fn fills_in_range_gaps() {
         let file = "line 1\n  second line";
@@ -515,4 +880,53 @@ Fourth and final line";
             ]
         );
     }
+
+    #[test]
+    fn aligns_split_diff_rows() {
+        let line = |status: DiffStatus, src: Option<usize>, dst: Option<usize>| LineDiff {
+            fragments: Vec::new(),
+            status,
+            src_line_number: src,
+            dst_line_number: dst,
+            moved: None,
+        };
+        let hunk = DiffHunk {
+            header: None,
+            lines: vec![
+                line(DiffStatus::Added, None, Some(1)),
+                line(DiffStatus::Unmodified, Some(1), Some(2)),
+                line(DiffStatus::Removed, Some(2), None),
+                line(DiffStatus::Removed, Some(3), None),
+                line(DiffStatus::Added, None, Some(3)),
+                line(DiffStatus::Unmodified, Some(4), Some(4)),
+                line(DiffStatus::Removed, Some(5), None),
+                line(DiffStatus::Added, None, Some(5)),
+                line(DiffStatus::Added, None, Some(6)),
+            ],
+        };
+
+        let rows: Vec<_> = SplitDiffHunk::from(hunk)
+            .rows
+            .into_iter()
+            .map(|row| {
+                (
+                    row.src.and_then(|l| l.src_line_number),
+                    row.dst.and_then(|l| l.dst_line_number),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            rows,
+            vec![
+                (None, Some(1)),
+                (Some(1), Some(2)),
+                (Some(2), Some(3)),
+                (Some(3), None),
+                (Some(4), Some(4)),
+                (Some(5), Some(5)),
+                (None, Some(6)),
+            ]
+        );
+    }
 }
@@ -1,6 +1,6 @@
 use std::{ops::Range, str::FromStr};
 
-use super::{diff_status::DiffStatus, hash::GitHash};
+use super::{diff_status::DiffStatus, hash::GitHash, submodule::SUBMODULE_MODE};
 
 type Line = (Option<usize>, Option<usize>, DiffStatus);
 
@@ -29,10 +29,45 @@ pub struct FileDiffMeta {
     pub dst_path: String,
     pub src_hash: Option<GitHash>,
     pub dst_hash: Option<GitHash>,
+    /// True if git considers the file binary, either from its contents or from `.gitattributes`
+    pub binary: bool,
+    /// True if the file is a submodule, in which case the hashes are commits in the submodule
+    pub submodule: bool,
 
     pub hunks: Vec<Hunk>,
 }
 
+/// A line in a combined diff of a merge commit against all of its parents
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CombinedLine {
+    /// Status of the line compared to each parent
+    pub statuses: Vec<DiffStatus>,
+    /// Line number in each parent, None if the line isn't in that parent
+    pub src_line_numbers: Vec<Option<usize>>,
+    /// None if the line was removed from the result
+    pub dst_line_number: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CombinedHunk {
+    /// Raw header text
+    pub header: String,
+    pub lines: Vec<CombinedLine>,
+}
+
+/// Header and hunks of a combined diff (`diff --cc` or `diff --combined`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CombinedFileDiffMeta {
+    pub path: String,
+    /// Hash of the file in each parent, None if it doesn't exist in that parent
+    pub src_hashes: Vec<Option<GitHash>>,
+    pub dst_hash: Option<GitHash>,
+    /// True if git considers the file binary, either from its contents or from `.gitattributes`
+    pub binary: bool,
+
+    pub hunks: Vec<CombinedHunk>,
+}
+
 impl HunkSection {
     fn append_line(&mut self, line: (Option<usize>, Option<usize>)) {
         match (self, line) {
@@ -162,24 +197,45 @@ impl FromStr for FileDiffMeta {
     type Err = String;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut sections = s.split("\n@@");
-        let mut diff_header = sections.next().ok_or("Failed to get diff header")?.lines();
-        let (src_path, dst_path) = diff_header
+        let diff_header: Vec<&str> = sections
             .next()
-            .unwrap()
+            .ok_or("Failed to get diff header")?
+            .lines()
+            .collect();
+        let (src_path, dst_path) = diff_header
+            .first()
+            .ok_or("Failed to get GNU diff command")?
             .strip_prefix("diff --git ")
             .ok_or("Failed to get GNU diff command")?
             .split_once(' ')
             .ok_or("Failed to split diff files")?;
 
-        let (src_hash, dst_hash) = diff_header
+        let mut index_line = diff_header
+            .iter()
             .find(|line| line.starts_with("index "))
             .ok_or("Failed to get index line")?
             .split(' ')
-            .nth(1)
-            .unwrap()
+            .skip(1);
+        let (src_hash, dst_hash) = index_line
+            .next()
+            .ok_or("Failed to get hashes")?
             .split_once("..")
             .ok_or("Failed to split hashes")?;
 
+        // The mode is on the index line if it didn't change, or on its own line if the file was
+        // added or deleted
+        let submodule = index_line.next() == Some(SUBMODULE_MODE)
+            || diff_header.iter().any(|line| {
+                line.strip_prefix("new file mode ")
+                    .or(line.strip_prefix("deleted file mode "))
+                    == Some(SUBMODULE_MODE)
+            });
+
+        // Binary files have no hunks, only a line saying they differ
+        let binary = diff_header
+            .iter()
+            .any(|line| line.starts_with("Binary files "));
+
         Ok(Self {
             src_path: src_path
                 .strip_prefix("a/")
@@ -191,6 +247,8 @@ impl FromStr for FileDiffMeta {
                 .into(),
             src_hash: GitHash::from_optional(src_hash)?,
             dst_hash: GitHash::from_optional(dst_hash)?,
+            binary,
+            submodule,
             hunks: sections
                 .map(|hunk| hunk.parse())
                 .collect::<Result<_, _>>()?,
@@ -198,10 +256,129 @@ impl FromStr for FileDiffMeta {
     }
 }
 
+impl FromStr for CombinedHunk {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+
+        // Combined hunk headers have one more `@` than there are parents, e.g. `@@@ -1,2 -1,3 +1,4 @@@`
+        let header = lines.next().ok_or("Failed to get hunk header")?;
+        let marker = "@".repeat(header.chars().take_while(|c| *c == '@').count());
+        let parents = marker.len() - 1;
+        if parents < 2 {
+            return Err("Combined hunk header has too few columns".into());
+        }
+
+        let mut starts = header
+            .split(marker.as_str())
+            .nth(1)
+            .ok_or("Failed to split combined hunk header")?
+            .trim()
+            .split(' ')
+            .map(|range| {
+                range
+                    .get(1..)
+                    .and_then(|range| range.split(',').next())
+                    .and_then(|line_number| line_number.parse::<usize>().ok())
+                    .ok_or(format!("Failed to parse combined hunk range: {range}"))
+            });
+        let mut src_line_numbers = (0..parents)
+            .map(|_| {
+                starts
+                    .next()
+                    .ok_or("Failed to get src from combined hunk header")?
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let mut dst_line_number = starts
+            .next()
+            .ok_or("Failed to get dst from combined hunk header")??;
+
+        let lines = lines
+            .filter(|line| *line != "\\ No newline at end of file")
+            .map(|line| {
+                // Each parent has its own status column, and blank context lines may be trimmed
+                let statuses = (0..parents)
+                    .map(|i| line.chars().nth(i).unwrap_or(' ').try_into())
+                    .collect::<Result<Vec<DiffStatus>, String>>()?;
+
+                // Lines removed from any parent aren't in the result
+                let in_dst = !statuses.contains(&DiffStatus::Removed);
+                let line = CombinedLine {
+                    src_line_numbers: statuses
+                        .iter()
+                        .zip(src_line_numbers.iter_mut())
+                        .map(|(status, line_number)| {
+                            let in_src = match status {
+                                DiffStatus::Removed => true,
+                                DiffStatus::Added => false,
+                                DiffStatus::Unmodified => in_dst,
+                            };
+                            in_src.then(|| {
+                                *line_number += 1;
+                                *line_number - 1
+                            })
+                        })
+                        .collect(),
+                    dst_line_number: in_dst.then(|| {
+                        dst_line_number += 1;
+                        dst_line_number - 1
+                    }),
+                    statuses,
+                };
+                Ok(line)
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Self {
+            header: header.into(),
+            lines,
+        })
+    }
+}
+
+impl FromStr for CombinedFileDiffMeta {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut sections = s.split("\n@@");
+        let mut diff_header = sections.next().ok_or("Failed to get diff header")?.lines();
+        let command = diff_header.next().unwrap();
+        let path = command
+            .strip_prefix("diff --cc ")
+            .or_else(|| command.strip_prefix("diff --combined "))
+            .ok_or("Failed to get combined diff command")?;
+
+        let (src_hashes, dst_hash) = diff_header
+            .find(|line| line.starts_with("index "))
+            .ok_or("Failed to get index line")?
+            .split(' ')
+            .nth(1)
+            .unwrap()
+            .split_once("..")
+            .ok_or("Failed to split hashes")?;
+
+        // Binary files have no hunks, only a line saying they differ
+        let binary = diff_header.any(|line| line.starts_with("Binary files "));
+
+        Ok(Self {
+            path: path.into(),
+            src_hashes: src_hashes
+                .split(',')
+                .map(GitHash::from_optional)
+                .collect::<Result<_, _>>()?,
+            dst_hash: GitHash::from_optional(dst_hash)?,
+            binary,
+            hunks: sections
+                .map(|hunk| format!("@@{hunk}").parse())
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::structures::{
-        file_diff_meta::{Hunk, HunkSection},
+        diff_status::DiffStatus,
+        file_diff_meta::{CombinedFileDiffMeta, CombinedHunk, CombinedLine, Hunk, HunkSection},
         hash::GitHash,
     };
 
@@ -231,6 +408,8 @@ index 628f0e2e8d64bae420b909c688e0964f7f530306..87eab99c6eca0c299bdf0fa8185d4f0d
                 dst_path: "src-tauri/src/commands/mod.rs".into(),
                 src_hash: Some(GitHash("628f0e2e8d64bae420b909c688e0964f7f530306".into())),
                 dst_hash: Some(GitHash("87eab99c6eca0c299bdf0fa8185d4f0d40255b73".into())),
+                binary: false,
+                submodule: false,
                 hunks: vec![Hunk {
                     header: "@@ -24,8 +24,8 @@ pub enum CommandError {".into(),
                     src_lines: 24..32,
@@ -244,4 +423,116 @@ index 628f0e2e8d64bae420b909c688e0964f7f530306..87eab99c6eca0c299bdf0fa8185d4f0d
             }
         );
     }
+
+    #[test]
+    fn parses_a_binary_file() {
+        let s = r#"diff --git a/public/icon.png b/public/icon.png
+new file mode 100644
+index 0000000000000000000000000000000000000000..53f9e1d3ec6f8ec9b2bd2c0e3d3d1ea0f6f4f5f2
+Binary files /dev/null and b/public/icon.png differ
+"#;
+        assert_eq!(
+            s.parse::<FileDiffMeta>().unwrap(),
+            FileDiffMeta {
+                src_path: "public/icon.png".into(),
+                dst_path: "public/icon.png".into(),
+                src_hash: None,
+                dst_hash: Some(GitHash("53f9e1d3ec6f8ec9b2bd2c0e3d3d1ea0f6f4f5f2".into())),
+                binary: true,
+                submodule: false,
+                hunks: vec![]
+            }
+        );
+    }
+
+    #[test]
+    fn parses_a_moved_submodule() {
+        let s = "diff --git a/libs/core b/libs/core
+index d6b791fb7420c481a7fda55dae90634541d3f7fe..f52aec8e19baa83f962a0c9e404238a002f2120f 160000
+--- a/libs/core
++++ b/libs/core
+@@ -1 +1 @@
+-Subproject commit d6b791fb7420c481a7fda55dae90634541d3f7fe
++Subproject commit f52aec8e19baa83f962a0c9e404238a002f2120f
+";
+        let meta = s.parse::<FileDiffMeta>().unwrap();
+        assert!(meta.submodule);
+        assert_eq!(
+            meta.src_hash,
+            Some(GitHash("d6b791fb7420c481a7fda55dae90634541d3f7fe".into()))
+        );
+        assert_eq!(
+            meta.dst_hash,
+            Some(GitHash("f52aec8e19baa83f962a0c9e404238a002f2120f".into()))
+        );
+    }
+
+    #[test]
+    fn parses_a_combined_diff() {
+        let s = r#"diff --cc src/main.rs
+index 1111111111111111111111111111111111111111,2222222222222222222222222222222222222222..3333333333333333333333333333333333333333
+--- a/src/main.rs
++++ b/src/main.rs
+@@@ -1,3 -1,3 +1,3 @@@
+  fn main() {
+-    println!("ours");
+ -    println!("theirs");
+++    println!("resolved");
+  }"#;
+        use DiffStatus::{Added, Removed, Unmodified};
+        let line =
+            |statuses: [DiffStatus; 2], src: [Option<usize>; 2], dst: Option<usize>| CombinedLine {
+                statuses: statuses.into(),
+                src_line_numbers: src.into(),
+                dst_line_number: dst,
+            };
+
+        assert_eq!(
+            s.parse::<CombinedFileDiffMeta>().unwrap(),
+            CombinedFileDiffMeta {
+                path: "src/main.rs".into(),
+                src_hashes: vec![
+                    Some(GitHash("1111111111111111111111111111111111111111".into())),
+                    Some(GitHash("2222222222222222222222222222222222222222".into())),
+                ],
+                dst_hash: Some(GitHash("3333333333333333333333333333333333333333".into())),
+                binary: false,
+                hunks: vec![CombinedHunk {
+                    header: "@@@ -1,3 -1,3 +1,3 @@@".into(),
+                    lines: vec![
+                        line([Unmodified, Unmodified], [Some(1), Some(1)], Some(1)),
+                        line([Removed, Unmodified], [Some(2), None], None),
+                        line([Unmodified, Removed], [None, Some(2)], None),
+                        line([Added, Added], [None, None], Some(2)),
+                        line([Unmodified, Unmodified], [Some(3), Some(3)], Some(3)),
+                    ]
+                }]
+            }
+        );
+    }
+
+    #[test]
+    fn parses_an_octopus_hunk_header() {
+        // From `git show --diff-merges=c` of a merge with three parents
+        let hunk = "@@@@ -2,5 -2,4 -2,6 +2,6 @@@@ fn main() \n       let x = 0;\n       let y = 0;\n       let z = 0;\n+++    let a = 1;\n +     let b = 2;\n  -    let c = 3;\n   }"
+            .parse::<CombinedHunk>()
+            .unwrap();
+        assert_eq!(hunk.lines.len(), 7);
+        assert_eq!(
+            hunk.lines[0].src_line_numbers,
+            vec![Some(2), Some(2), Some(2)]
+        );
+        assert_eq!(hunk.lines[0].dst_line_number, Some(2));
+        assert_eq!(hunk.lines[3].src_line_numbers, vec![None, None, None]);
+        assert_eq!(hunk.lines[3].dst_line_number, Some(5));
+        assert_eq!(hunk.lines[4].src_line_numbers, vec![Some(5), None, Some(5)]);
+        assert_eq!(hunk.lines[4].dst_line_number, Some(6));
+        assert_eq!(hunk.lines[5].src_line_numbers, vec![None, None, Some(6)]);
+        assert_eq!(hunk.lines[5].dst_line_number, None);
+        assert_eq!(
+            hunk.lines[6].src_line_numbers,
+            vec![Some(6), Some(5), Some(7)]
+        );
+        assert_eq!(hunk.lines[6].dst_line_number, Some(7));
+    }
 }
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use specta::Type;
+
+use super::file::File;
+
+/// Number of lines changed in a file
+#[derive(Debug, Serialize, Type, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FileStats {
+    pub insertions: usize,
+    pub deletions: usize,
+    /// Binary files have no line counts
+    pub binary: bool,
+}
+
+/// Total lines changed across all files in a diff
+#[derive(Debug, Serialize, Type, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiffStats {
+    pub files_changed: usize,
+    pub insertions: usize,
+    pub deletions: usize,
+}
+
+impl FileStats {
+    /// Parse the output of a diff with `--numstat -z`, keyed by the path of the file after the change
+    pub fn from_numstat(numstat: &str) -> Result<HashMap<String, Self>, String> {
+        let mut stats = HashMap::new();
+        let mut entries = numstat.split('\x00').filter(|entry| !entry.is_empty());
+        while let Some(entry) = entries.next() {
+            let mut parts = entry.splitn(3, '\t');
+            let insertions = parts.next().ok_or("Failed to get numstat insertions")?;
+            let deletions = parts.next().ok_or("Failed to get numstat deletions")?;
+            let path = match parts.next() {
+                // Renames and copies have an empty path followed by the src and dst paths
+                Some("") => {
+                    entries.next().ok_or("Failed to get numstat src path")?;
+                    entries.next().ok_or("Failed to get numstat dst path")?
+                }
+                Some(path) => path,
+                None => return Err("Failed to get numstat path".into()),
+            };
+
+            // Binary files show `-` instead of line counts
+            let binary = insertions == "-" && deletions == "-";
+            let parse = |count: &str| {
+                count
+                    .parse()
+                    .map_err(|err| format!("Failed to parse numstat count: {err}"))
+            };
+            stats.insert(
+                path.into(),
+                if binary {
+                    Self {
+                        binary,
+                        ..Self::default()
+                    }
+                } else {
+                    Self {
+                        insertions: parse(insertions)?,
+                        deletions: parse(deletions)?,
+                        binary,
+                    }
+                },
+            );
+        }
+        Ok(stats)
+    }
+}
+
+impl DiffStats {
+    pub fn from_files(files: &[File]) -> Self {
+        files
+            .iter()
+            .fold(Self::default(), |total, file| match &file.stats {
+                Some(stats) => Self {
+                    files_changed: total.files_changed + 1,
+                    insertions: total.insertions + stats.insertions,
+                    deletions: total.deletions + stats.deletions,
+                },
+                None => Self {
+                    files_changed: total.files_changed + 1,
+                    ..total
+                },
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FileStats;
+
+    #[test]
+    fn parses_numstat() {
+        let stats = FileStats::from_numstat(
+            "3\t1\tsrc/main.rs\x000\t0\t\x00old.rs\x00new.rs\x00-\t-\timage.png\x00",
+        )
+        .unwrap();
+        assert_eq!(stats.len(), 3);
+        assert_eq!(
+            stats["src/main.rs"],
+            FileStats {
+                insertions: 3,
+                deletions: 1,
+                binary: false
+            }
+        );
+        assert_eq!(stats["new.rs"], FileStats::default());
+        assert_eq!(
+            stats["image.png"],
+            FileStats {
+                insertions: 0,
+                deletions: 0,
+                binary: true
+            }
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// A progress line reported by git on stderr, e.g. `Receiving objects:  45% (450/1000)`
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct GitProgress {
+    /// e.g. "Counting objects" or "Resolving deltas"
+    pub phase: String,
+    pub percentage: Option<u8>,
+    /// Number of objects processed so far
+    pub current: Option<u64>,
+    /// None if git doesn't know the total yet
+    pub total: Option<u64>,
+}
+
+impl FromStr for GitProgress {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Progress from the remote is prefixed, but is otherwise the same
+        let s = s.trim().trim_start_matches("remote: ");
+        let (phase, rest) = s.split_once(": ").ok_or("Not a progress line")?;
+        let rest = rest.trim_start();
+
+        let (percentage, current, total) = match rest.split_once('%') {
+            // `45% (450/1000), ...`
+            Some((percentage, counts)) => {
+                let (current, total) = counts
+                    .trim_start()
+                    .strip_prefix('(')
+                    .and_then(|counts| counts.split_once(')'))
+                    .and_then(|(counts, _)| counts.split_once('/'))
+                    .ok_or("Failed to get progress counts")?;
+                (
+                    Some(
+                        percentage
+                            .parse()
+                            .map_err(|err| format!("Failed to parse percentage: {err}"))?,
+                    ),
+                    Some(
+                        current
+                            .parse()
+                            .map_err(|err| format!("Failed to parse progress count: {err}"))?,
+                    ),
+                    Some(
+                        total
+                            .parse()
+                            .map_err(|err| format!("Failed to parse progress total: {err}"))?,
+                    ),
+                )
+            }
+            // `450` or `450, done.` when the total isn't known
+            None => (
+                None,
+                Some(
+                    rest.split(',')
+                        .next()
+                        .unwrap_or_default()
+                        .parse()
+                        .map_err(|err| format!("Failed to parse progress count: {err}"))?,
+                ),
+                None,
+            ),
+        };
+
+        Ok(Self {
+            phase: phase.into(),
+            percentage,
+            current,
+            total,
+        })
+    }
+}
+
+impl fmt::Display for GitProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:", self.phase)?;
+        if let Some(percentage) = self.percentage {
+            write!(f, " {percentage}%")?;
+        }
+        match (self.current, self.total) {
+            (Some(current), Some(total)) => write!(f, " ({current}/{total})"),
+            (Some(current), None) => write!(f, " {current}"),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::GitProgress;
+
+    #[test]
+    fn parses_progress_lines() {
+        assert_eq!(
+            "Receiving objects:  45% (450/1000), 1.20 MiB | 512.00 KiB/s"
+                .parse::<GitProgress>()
+                .unwrap(),
+            GitProgress {
+                phase: "Receiving objects".into(),
+                percentage: Some(45),
+                current: Some(450),
+                total: Some(1000),
+            }
+        );
+        assert_eq!(
+            "remote: Enumerating objects: 12, done."
+                .parse::<GitProgress>()
+                .unwrap(),
+            GitProgress {
+                phase: "Enumerating objects".into(),
+                percentage: None,
+                current: Some(12),
+                total: None,
+            }
+        );
+    }
+
+    #[test]
+    fn ignores_other_lines() {
+        assert!("From github.com:user/repo".parse::<GitProgress>().is_err());
+        assert!("error: could not read from remote repository"
+            .parse::<GitProgress>()
+            .is_err());
+    }
+}
@@ -0,0 +1,98 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Oldest version of git that supports every command this app runs
+pub const MINIMUM_GIT_VERSION: GitVersion = GitVersion {
+    major: 2,
+    minor: 31,
+    patch: 0,
+};
+
+/// Oldest version of git that can read attributes from a commit with `check-attr --source`
+pub const CHECK_ATTR_SOURCE_VERSION: GitVersion = GitVersion {
+    major: 2,
+    minor: 40,
+    patch: 0,
+};
+
+/// Fields are ordered so versions compare correctly
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GitVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl FromStr for GitVersion {
+    type Err = String;
+    /// Parse the output of `git --version`, e.g. `git version 2.39.3 (Apple Git-145)`
+    /// or `git version 2.45.1.windows.1`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let version = s
+            .trim()
+            .strip_prefix("git version ")
+            .ok_or("Not a git version")?;
+        let mut parts = version
+            .split(|c: char| !c.is_ascii_digit())
+            .map(|part| part.parse::<u32>());
+
+        let mut next = |name: &str| {
+            parts
+                .next()
+                .unwrap_or(Ok(0))
+                .map_err(|err| format!("Failed to parse {name} version: {err}"))
+        };
+
+        Ok(Self {
+            major: next("major")?,
+            minor: next("minor")?,
+            patch: next("patch")?,
+        })
+    }
+}
+
+impl fmt::Display for GitVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::GitVersion;
+
+    #[test]
+    fn parses_git_versions() {
+        let version = |major, minor, patch| GitVersion {
+            major,
+            minor,
+            patch,
+        };
+        assert_eq!(
+            "git version 2.39.3 (Apple Git-145)\n"
+                .parse::<GitVersion>()
+                .unwrap(),
+            version(2, 39, 3)
+        );
+        assert_eq!(
+            "git version 2.45.1.windows.1"
+                .parse::<GitVersion>()
+                .unwrap(),
+            version(2, 45, 1)
+        );
+        assert_eq!(
+            "git version 2.50.0-rc1".parse::<GitVersion>().unwrap(),
+            version(2, 50, 0)
+        );
+        assert!("hub version 2.14.2".parse::<GitVersion>().is_err());
+    }
+
+    #[test]
+    fn compares_versions() {
+        let older: GitVersion = "git version 2.9.5".parse().unwrap();
+        let newer: GitVersion = "git version 2.31.0".parse().unwrap();
+        assert!(older < newer);
+    }
+}
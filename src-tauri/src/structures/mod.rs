@@ -1,9 +1,20 @@
 pub mod branch;
+pub mod branch_cleanup;
 pub mod commit;
 pub mod diff_status;
 pub mod file;
 pub mod file_diff;
 pub mod file_diff_meta;
+pub mod file_stats;
 pub mod file_status;
+pub mod git_progress;
+pub mod git_version;
 pub mod hash;
+pub mod moved_block;
+pub mod patch;
+pub mod remote;
+pub mod repository_status;
+pub mod revision_comparison;
+pub mod submodule;
 pub mod upstream_track;
+pub mod worktree;
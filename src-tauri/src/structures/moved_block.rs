@@ -0,0 +1,247 @@
+use std::{collections::HashMap, ops::Range};
+
+use serde::Serialize;
+use specta::Type;
+
+use super::file_diff_meta::{FileDiffMeta, HunkSection};
+
+/// Blocks with fewer alphanumeric characters than this aren't considered moved, same as git
+const MIN_ALNUM_COUNT: usize = 20;
+
+/// Diffs with more removed or added lines than this aren't checked for moved blocks, as comparing
+/// every removed line to every added line would take too long
+const MAX_CHANGED_LINES: usize = 5000;
+
+/// A removed or added line from a diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedLine {
+    pub path: String,
+    pub line_number: usize,
+    pub text: String,
+}
+
+/// A block of lines that was removed from one location and added at another
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MovedBlock {
+    pub src_path: String,
+    pub src_lines: Range<usize>,
+    pub dst_path: String,
+    pub dst_lines: Range<usize>,
+}
+
+/// Annotation for a line that's part of a moved block, pointing to where the block was moved to or from
+#[derive(Debug, Serialize, Type, Clone)]
+pub struct MovedLine {
+    /// Index of the moved block, so adjacent blocks can be coloured differently (like git's zebra mode)
+    pub block: usize,
+    /// Path of the file containing the other side of the move
+    pub path: String,
+    pub start_line_number: usize,
+    /// Exclusive
+    pub end_line_number: usize,
+}
+
+/// Get all removed and added lines from a diff that may contain multiple files.
+/// Expects a diff without context lines (`-U0`).
+pub fn changed_lines(diff: &str) -> (Vec<ChangedLine>, Vec<ChangedLine>) {
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+
+    for file in diff
+        .split("\ndiff --git ")
+        .map(|file| format!("diff --git {}", file.trim_start_matches("diff --git ")))
+    {
+        // Files without text hunks (e.g. renames without changes) won't parse
+        let Ok(meta) = file.parse::<FileDiffMeta>() else {
+            continue;
+        };
+
+        let (mut removed_line_numbers, mut added_line_numbers) = (Vec::new(), Vec::new());
+        for section in meta.hunks.iter().flat_map(|hunk| hunk.sections.iter()) {
+            match section {
+                HunkSection::Removed(lines) => removed_line_numbers.extend(lines),
+                HunkSection::Added(lines) => added_line_numbers.extend(lines),
+                HunkSection::RemovedAdded(removed_lines, added_lines) => {
+                    removed_line_numbers.extend(removed_lines);
+                    added_line_numbers.extend(added_lines);
+                }
+                HunkSection::Unmodified(_) => {}
+            }
+        }
+
+        // Without context lines, the hunk body only contains removed and added lines, in order
+        let body = file
+            .split("\n@@")
+            .skip(1)
+            .flat_map(|hunk| hunk.lines().skip(1))
+            .collect::<Vec<_>>();
+        removed.extend(
+            body.iter()
+                .filter_map(|line| line.strip_prefix('-'))
+                .zip(removed_line_numbers)
+                .map(|(text, line_number)| ChangedLine {
+                    path: meta.src_path.clone(),
+                    line_number,
+                    text: text.into(),
+                }),
+        );
+        added.extend(
+            body.iter()
+                .filter_map(|line| line.strip_prefix('+'))
+                .zip(added_line_numbers)
+                .map(|(text, line_number)| ChangedLine {
+                    path: meta.dst_path.clone(),
+                    line_number,
+                    text: text.into(),
+                }),
+        );
+    }
+
+    (removed, added)
+}
+
+/// Find blocks of consecutive removed lines that match blocks of consecutive added lines
+pub fn detect_moved_blocks(removed: &[ChangedLine], added: &[ChangedLine]) -> Vec<MovedBlock> {
+    if removed.len() > MAX_CHANGED_LINES || added.len() > MAX_CHANGED_LINES {
+        return Vec::new();
+    }
+
+    let mut added_by_text: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, line) in added.iter().enumerate() {
+        added_by_text.entry(&line.text).or_default().push(i);
+    }
+
+    let is_next = |lines: &[ChangedLine], i: usize| {
+        i > 0
+            && i < lines.len()
+            && lines[i].path == lines[i - 1].path
+            && lines[i].line_number == lines[i - 1].line_number + 1
+    };
+
+    let mut added_used = vec![false; added.len()];
+    let mut blocks = Vec::new();
+    let mut i = 0;
+    while i < removed.len() {
+        // Lines like `}` or blank ones match nearly everywhere, so blocks can contain them but
+        // don't start at them
+        if !removed[i].text.chars().any(char::is_alphanumeric) {
+            i += 1;
+            continue;
+        }
+
+        // Find the longest run of matching lines starting at this removed line
+        let longest = added_by_text
+            .get(removed[i].text.as_str())
+            .into_iter()
+            .flatten()
+            .filter(|j| !added_used[**j])
+            .map(|&j| {
+                let mut len = 1;
+                while is_next(removed, i + len)
+                    && is_next(added, j + len)
+                    && !added_used[j + len]
+                    && removed[i + len].text == added[j + len].text
+                {
+                    len += 1;
+                }
+                (j, len)
+            })
+            .max_by_key(|(_, len)| *len);
+
+        match longest {
+            Some((j, len))
+                if removed[i..i + len]
+                    .iter()
+                    .flat_map(|line| line.text.chars())
+                    .filter(|c| c.is_alphanumeric())
+                    .count()
+                    >= MIN_ALNUM_COUNT =>
+            {
+                added_used[j..j + len].fill(true);
+                blocks.push(MovedBlock {
+                    src_path: removed[i].path.clone(),
+                    src_lines: removed[i].line_number..removed[i].line_number + len,
+                    dst_path: added[j].path.clone(),
+                    dst_lines: added[j].line_number..added[j].line_number + len,
+                });
+                i += len;
+            }
+            _ => i += 1,
+        }
+    }
+
+    blocks
+}
+
+#[cfg(test)]
+mod test {
+    use super::{changed_lines, detect_moved_blocks, ChangedLine, MovedBlock, MAX_CHANGED_LINES};
+
+    #[test]
+    fn detects_blocks_moved_between_files() {
+        let diff = r#"diff --git a/src/a.rs b/src/a.rs
+index 1111111111111111111111111111111111111111..2222222222222222222222222222222222222222 100644
+--- a/src/a.rs
++++ b/src/a.rs
+@@ -3,4 +2,0 @@ fn main() {
+-fn parse_number(input: &str) -> usize {
+-    input.trim().parse().unwrap()
+-}
+-
+diff --git a/src/b.rs b/src/b.rs
+index 3333333333333333333333333333333333333333..4444444444444444444444444444444444444444 100644
+--- a/src/b.rs
++++ b/src/b.rs
+@@ -10 +10,4 @@ use std::str::FromStr;
+-const UNRELATED: usize = 1;
++fn parse_number(input: &str) -> usize {
++    input.trim().parse().unwrap()
++}
++const UNRELATED: usize = 2;
+"#;
+        let (removed, added) = changed_lines(diff);
+        assert_eq!(removed.len(), 5);
+        assert_eq!(added.len(), 4);
+
+        assert_eq!(
+            detect_moved_blocks(&removed, &added),
+            vec![MovedBlock {
+                src_path: "src/a.rs".into(),
+                src_lines: 3..6,
+                dst_path: "src/b.rs".into(),
+                dst_lines: 10..13,
+            }]
+        );
+    }
+
+    #[test]
+    fn ignores_short_blocks() {
+        let diff = r#"diff --git a/a.txt b/a.txt
+index 1111111111111111111111111111111111111111..2222222222222222222222222222222222222222 100644
+--- a/a.txt
++++ b/a.txt
+@@ -1 +0,0 @@
+-}
+@@ -5,0 +5 @@
++}
+"#;
+        let (removed, added) = changed_lines(diff);
+        assert_eq!(detect_moved_blocks(&removed, &added), vec![]);
+    }
+
+    #[test]
+    fn skips_diffs_with_too_many_changed_lines() {
+        let lines = |path: &str| {
+            (1..=MAX_CHANGED_LINES + 1)
+                .map(|line_number| ChangedLine {
+                    path: path.into(),
+                    line_number,
+                    text: format!("let value_{line_number} = compute({line_number});"),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert!(detect_moved_blocks(&lines("a.rs"), &lines("b.rs")).is_empty());
+        assert!(!detect_moved_blocks(&lines("a.rs")[1..], &lines("b.rs")[1..]).is_empty());
+    }
+}
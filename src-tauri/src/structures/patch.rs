@@ -0,0 +1,141 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use super::hash::GitHash;
+
+/// Commits to export as patch files
+#[derive(Debug, Deserialize, Type, Clone)]
+pub enum PatchRevisions {
+    /// A single commit
+    Commit(GitHash),
+    /// Commits reachable from head but not base (`base..head`)
+    Range { base: String, head: String },
+}
+
+#[derive(Debug, Deserialize, Type, Clone, Copy)]
+pub enum PatchApplyMethod {
+    /// Apply the changes to the working tree with `git apply`
+    Apply,
+    /// Create commits from an mbox with `git am`, keeping the original authors and messages
+    Am,
+}
+
+/// Why a patch or one of its hunks can't be applied
+#[derive(Debug, Serialize, Type, Clone, PartialEq, Eq)]
+pub struct PatchFailure {
+    pub path: String,
+    /// Line the hunk was expected at, None if the whole file failed
+    pub line_number: Option<usize>,
+    pub reason: String,
+    /// Lines git couldn't find in the file when applying the hunk
+    pub expected_lines: Option<String>,
+}
+
+/// Result of a dry run of applying a patch
+#[derive(Debug, Serialize, Type, Clone, PartialEq, Eq, Default)]
+pub struct PatchCheck {
+    /// Empty if the patch applies cleanly
+    pub failures: Vec<PatchFailure>,
+}
+
+impl FromStr for PatchCheck {
+    type Err = String;
+    /// Parse the stderr output of `git apply --check -v`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut failures = Vec::new();
+        let mut expected_lines: Option<Vec<&str>> = None;
+        for line in s.lines() {
+            let Some(error) = line.strip_prefix("error: ") else {
+                // Lines after "while searching for:" are the hunk's context and removed lines
+                if let Some(expected_lines) = &mut expected_lines {
+                    expected_lines.push(line);
+                }
+                continue;
+            };
+
+            if error == "while searching for:" {
+                expected_lines = Some(Vec::new());
+            } else if let Some(location) = error.strip_prefix("patch failed: ") {
+                let (path, line_number) = location
+                    .rsplit_once(':')
+                    .ok_or("Failed to split patch failure location")?;
+                failures.push(PatchFailure {
+                    path: path.into(),
+                    line_number: Some(
+                        line_number
+                            .parse()
+                            .map_err(|err| format!("Failed to parse patch failure line: {err}"))?,
+                    ),
+                    reason: "Lines in the file don't match the hunk".into(),
+                    expected_lines: expected_lines
+                        .take()
+                        .map(|lines| lines.join("\n").trim_end().into()),
+                });
+            } else if let Some((path, reason)) = error.split_once(": ") {
+                // A hunk failure for this file already explains why it doesn't apply
+                if reason == "patch does not apply"
+                    && failures.iter().any(|failure| failure.path == path)
+                {
+                    continue;
+                }
+                failures.push(PatchFailure {
+                    path: path.into(),
+                    line_number: None,
+                    reason: reason.into(),
+                    expected_lines: None,
+                });
+            } else {
+                return Err(format!("Unrecognised patch error: {error}"));
+            }
+        }
+
+        Ok(Self { failures })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{PatchCheck, PatchFailure};
+
+    #[test]
+    fn parses_failed_hunks() {
+        let s = r#"Checking patch src/main.rs...
+error: while searching for:
+fn main() {
+    println!("hello");
+}
+
+error: patch failed: src/main.rs:12
+error: src/main.rs: patch does not apply
+Checking patch README.md...
+error: README.md: does not exist in index
+"#;
+        assert_eq!(
+            s.parse::<PatchCheck>().unwrap(),
+            PatchCheck {
+                failures: vec![
+                    PatchFailure {
+                        path: "src/main.rs".into(),
+                        line_number: Some(12),
+                        reason: "Lines in the file don't match the hunk".into(),
+                        expected_lines: Some("fn main() {\n    println!(\"hello\");\n}".into()),
+                    },
+                    PatchFailure {
+                        path: "README.md".into(),
+                        line_number: None,
+                        reason: "does not exist in index".into(),
+                        expected_lines: None,
+                    }
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn parses_a_clean_patch() {
+        let s = "Checking patch src/main.rs...\nApplied patch src/main.rs cleanly.\n";
+        assert_eq!(s.parse::<PatchCheck>().unwrap(), PatchCheck::default());
+    }
+}
@@ -0,0 +1,98 @@
+use serde::Serialize;
+use specta::Type;
+
+#[derive(Debug, Serialize, Type, Clone, PartialEq, Eq)]
+pub struct Remote {
+    pub name: String,
+    pub fetch_urls: Vec<String>,
+    /// The same as `fetch_urls` unless push URLs are set separately
+    pub push_urls: Vec<String>,
+    /// e.g. `+refs/heads/*:refs/remotes/origin/*`
+    pub fetch_refspecs: Vec<String>,
+    /// Empty unless set, in which case they replace `push.default`
+    pub push_refspecs: Vec<String>,
+}
+
+impl Remote {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.into(),
+            fetch_urls: Vec::new(),
+            push_urls: Vec::new(),
+            fetch_refspecs: Vec::new(),
+            push_refspecs: Vec::new(),
+        }
+    }
+
+    /// Parse the remotes from the output of `git config -z --get-regexp`, in the order they're configured
+    pub fn from_config(s: &str) -> Result<Vec<Self>, String> {
+        let mut remotes: Vec<Self> = Vec::new();
+
+        for entry in s.split('\0').filter(|entry| !entry.is_empty()) {
+            let (key, value) = entry.split_once('\n').unwrap_or((entry, ""));
+            // Remote names can contain dots, but the variable name can't
+            let Some((name, variable)) = key
+                .strip_prefix("remote.")
+                .and_then(|key| key.rsplit_once('.'))
+            else {
+                continue;
+            };
+
+            let remote = match remotes.iter().position(|remote| remote.name == name) {
+                Some(index) => &mut remotes[index],
+                None => {
+                    remotes.push(Self::new(name));
+                    remotes.last_mut().ok_or("Failed to add remote")?
+                }
+            };
+            match variable {
+                "url" => remote.fetch_urls.push(value.into()),
+                "pushurl" => remote.push_urls.push(value.into()),
+                "fetch" => remote.fetch_refspecs.push(value.into()),
+                "push" => remote.push_refspecs.push(value.into()),
+                _ => {}
+            }
+        }
+
+        for remote in remotes.iter_mut() {
+            if remote.push_urls.is_empty() {
+                remote.push_urls = remote.fetch_urls.clone();
+            }
+        }
+
+        Ok(remotes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Remote;
+
+    #[test]
+    fn parses_remotes_from_config() {
+        let config = "core.bare\nfalse\0remote.origin.url\nhttps://example.com/a.git\0remote.origin.fetch\n+refs/heads/*:refs/remotes/origin/*\0remote.origin.pushurl\ngit@example.com:a.git\0remote.origin.fetch\n+refs/tags/*:refs/tags/*\0remote.up.stream.url\ngit@github.com:x/y.git\0remote.up.stream.fetch\n+refs/heads/*:refs/remotes/up.stream/*\0branch.main.remote\norigin\0";
+
+        assert_eq!(
+            Remote::from_config(config).unwrap(),
+            vec![
+                Remote {
+                    name: "origin".into(),
+                    fetch_urls: vec!["https://example.com/a.git".into()],
+                    push_urls: vec!["git@example.com:a.git".into()],
+                    fetch_refspecs: vec![
+                        "+refs/heads/*:refs/remotes/origin/*".into(),
+                        "+refs/tags/*:refs/tags/*".into()
+                    ],
+                    push_refspecs: vec![],
+                },
+                Remote {
+                    name: "up.stream".into(),
+                    fetch_urls: vec!["git@github.com:x/y.git".into()],
+                    push_urls: vec!["git@github.com:x/y.git".into()],
+                    fetch_refspecs: vec!["+refs/heads/*:refs/remotes/up.stream/*".into()],
+                    push_refspecs: vec![],
+                },
+            ]
+        );
+    }
+}
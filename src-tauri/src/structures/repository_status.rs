@@ -0,0 +1,79 @@
+use std::str::FromStr;
+
+/// Summary of `git status --porcelain=v2 --branch`
+#[derive(Debug, PartialEq, Eq)]
+pub struct RepositoryStatus {
+    /// Whether there are any staged, unstaged or untracked changes
+    pub has_changes: bool,
+    /// None if the current branch has no upstream
+    pub ahead: Option<i64>,
+    pub behind: Option<i64>,
+}
+
+impl FromStr for RepositoryStatus {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut status = Self {
+            has_changes: false,
+            ahead: None,
+            behind: None,
+        };
+
+        for line in s.lines() {
+            // `# branch.ab +1 -2`
+            if let Some(counts) = line.strip_prefix("# branch.ab ") {
+                let (ahead, behind) = counts
+                    .split_once(' ')
+                    .ok_or(format!("Unknown ahead/behind format: {counts}"))?;
+                status.ahead = Some(
+                    ahead
+                        .trim_start_matches('+')
+                        .parse()
+                        .map_err(|err| format!("Failed to parse ahead: {err}"))?,
+                );
+                status.behind = Some(
+                    behind
+                        .trim_start_matches('-')
+                        .parse()
+                        .map_err(|err| format!("Failed to parse behind: {err}"))?,
+                );
+            } else if !line.starts_with('#') && !line.is_empty() {
+                status.has_changes = true;
+            }
+        }
+
+        Ok(status)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RepositoryStatus;
+
+    #[test]
+    fn parses_status() {
+        let status: RepositoryStatus = "# branch.oid 8b1c0d2e4f\n# branch.head main\n# branch.upstream origin/main\n# branch.ab +2 -13\n1 .M N... 100644 100644 100644 3f2a 3f2a src/main.rs\n? notes.txt\n"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            status,
+            RepositoryStatus {
+                has_changes: true,
+                ahead: Some(2),
+                behind: Some(13),
+            }
+        );
+
+        let status: RepositoryStatus = "# branch.oid (initial)\n# branch.head main\n"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            status,
+            RepositoryStatus {
+                has_changes: false,
+                ahead: None,
+                behind: None,
+            }
+        );
+    }
+}
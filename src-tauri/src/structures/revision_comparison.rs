@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use super::{commit::Commit, file::File, hash::GitHash};
+
+#[derive(Debug, Deserialize, Type, Clone, Copy)]
+pub enum CompareMode {
+    /// Changes between the two revisions directly (`base..head`)
+    TwoDot,
+    /// Changes on head since it diverged from base (`base...head`)
+    ThreeDot,
+}
+
+#[derive(Debug, Serialize, Type)]
+pub struct RevisionComparison {
+    /// Commit the files are diffed from, which is the merge base in three-dot mode
+    pub src_hash: GitHash,
+    /// Commit the files are diffed to
+    pub dst_hash: GitHash,
+    pub files: Vec<File>,
+    /// Commits reachable from base but not head, newest first and at most a graph page of them
+    pub base_commits: Vec<Commit>,
+    /// Whether there are more base commits than fit in `base_commits`
+    pub base_commits_truncated: bool,
+    /// Commits reachable from head but not base, newest first and at most a graph page of them
+    pub head_commits: Vec<Commit>,
+    /// Whether there are more head commits than fit in `head_commits`
+    pub head_commits_truncated: bool,
+}
@@ -0,0 +1,269 @@
+use std::str::FromStr;
+
+use serde::Serialize;
+use specta::Type;
+
+use super::hash::GitHash;
+
+/// File mode git uses for submodules, called gitlinks
+pub const SUBMODULE_MODE: &str = "160000";
+
+/// How a submodule differs from the commit recorded for it
+#[derive(Debug, Serialize, Type, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SubmoduleChange {
+    /// The submodule moved to another commit
+    pub commit_changed: bool,
+    /// The submodule has changes to tracked files
+    pub modified_content: bool,
+    /// The submodule has untracked files
+    pub untracked_content: bool,
+}
+
+#[derive(Debug, Serialize, Type, Clone, PartialEq, Eq)]
+pub struct Submodule {
+    pub name: String,
+    /// Path relative to the top level of the repository
+    pub path: String,
+    /// As configured in `.gitmodules`, which can be relative to the repository's remote
+    pub url: Option<String>,
+    /// Commit recorded in the index, None if the submodule hasn't been added yet
+    pub recorded_commit: Option<GitHash>,
+    /// Commit checked out in the submodule, None if it hasn't been initialized and updated
+    pub checked_out_commit: Option<GitHash>,
+    pub change: SubmoduleChange,
+}
+
+impl FromStr for SubmoduleChange {
+    type Err = String;
+    /// Parse the `<sub>` field of `git status --porcelain=2`, e.g. `SC.U`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut flags = s
+            .strip_prefix('S')
+            .ok_or(format!("Not a submodule state: {s}"))?
+            .chars();
+        let mut flag = |set| {
+            flags
+                .next()
+                .map(|flag| flag == set)
+                .ok_or(format!("Submodule state is too short: {s}"))
+        };
+        Ok(Self {
+            commit_changed: flag('C')?,
+            modified_content: flag('M')?,
+            untracked_content: flag('U')?,
+        })
+    }
+}
+
+impl SubmoduleChange {
+    /// Parse the `<sub>` field of `git status --porcelain=2`, which is `N...` for regular files
+    pub fn from_status_field(s: &str) -> Result<Option<Self>, String> {
+        if s.starts_with('N') {
+            return Ok(None);
+        }
+        s.parse().map(Some)
+    }
+}
+
+impl Submodule {
+    pub fn new(name: &str, path: &str) -> Self {
+        Self {
+            name: name.into(),
+            path: path.into(),
+            url: None,
+            recorded_commit: None,
+            checked_out_commit: None,
+            change: SubmoduleChange::default(),
+        }
+    }
+
+    /// Parse the submodules from the output of `git config -z --file .gitmodules --list`
+    pub fn from_gitmodules(s: &str) -> Vec<Self> {
+        let mut submodules: Vec<Self> = Vec::new();
+
+        for entry in s.split('\0').filter(|entry| !entry.is_empty()) {
+            let (key, value) = entry.split_once('\n').unwrap_or((entry, ""));
+            // Submodule names are usually their path, so they can contain dots
+            let Some((name, variable)) = key
+                .strip_prefix("submodule.")
+                .and_then(|key| key.rsplit_once('.'))
+            else {
+                continue;
+            };
+
+            let index = match submodules
+                .iter()
+                .position(|submodule| submodule.name == name)
+            {
+                Some(index) => index,
+                None => {
+                    submodules.push(Self::new(name, name));
+                    submodules.len() - 1
+                }
+            };
+            match variable {
+                "path" => submodules[index].path = value.into(),
+                "url" => submodules[index].url = Some(value.into()),
+                _ => {}
+            }
+        }
+
+        submodules
+    }
+
+    /// Attach recorded commits from the output of `git ls-files --stage -z`
+    pub fn with_recorded_commits(
+        mut submodules: Vec<Self>,
+        ls_files: &str,
+    ) -> Result<Vec<Self>, String> {
+        for entry in ls_files.split('\0').filter(|entry| !entry.is_empty()) {
+            // `<mode> <hash> <stage>\t<path>`
+            let (info, path) = entry
+                .split_once('\t')
+                .ok_or(format!("Failed to split index entry: {entry}"))?;
+            let mut info = info.split(' ');
+            if info.next() != Some(SUBMODULE_MODE) {
+                continue;
+            }
+            let hash = info.next().ok_or("Failed to get index entry hash")?;
+
+            if let Some(submodule) = submodules
+                .iter_mut()
+                .find(|submodule| submodule.path == path)
+            {
+                submodule.recorded_commit = GitHash::from_optional(hash)?;
+            }
+        }
+        Ok(submodules)
+    }
+
+    /// Attach checked out commits from the output of `git submodule status`
+    pub fn with_checked_out_commits(
+        mut submodules: Vec<Self>,
+        status: &str,
+    ) -> Result<Vec<Self>, String> {
+        for line in status.lines() {
+            // `<prefix><hash> <path> (<describe>)`, where `-` means it's not initialized
+            let prefix = line
+                .chars()
+                .next()
+                .ok_or("Failed to get submodule prefix")?;
+            let (hash, path) = line
+                .get(1..)
+                .and_then(|line| line.split_once(' '))
+                .ok_or(format!("Failed to split submodule status: {line}"))?;
+            if prefix == '-' {
+                continue;
+            }
+
+            // Paths aren't quoted, so match the longest known path instead of splitting on spaces
+            if let Some(submodule) = submodules
+                .iter_mut()
+                .filter(|submodule| {
+                    path == submodule.path
+                        || path
+                            .strip_prefix(submodule.path.as_str())
+                            .is_some_and(|rest| rest.starts_with(" ("))
+                })
+                .max_by_key(|submodule| submodule.path.len())
+            {
+                submodule.checked_out_commit = GitHash::from_optional(hash)?;
+            }
+        }
+        Ok(submodules)
+    }
+
+    /// Attach changes from the output of `git status --porcelain=2 -z`
+    pub fn with_changes(mut submodules: Vec<Self>, status: &str) -> Result<Vec<Self>, String> {
+        for item in status.split('\0') {
+            // Renamed entries have an extra score field and unmerged ones have extra stages
+            let fields = match item.split(' ').next() {
+                Some("1") => 9,
+                Some("2") => 10,
+                Some("u") => 11,
+                _ => continue,
+            };
+            let parts: Vec<&str> = item.splitn(fields, ' ').collect();
+            let (Some(field), Some(path)) = (parts.get(2), parts.last()) else {
+                return Err(format!("Failed to split status entry: {item}"));
+            };
+
+            if let Some(change) = SubmoduleChange::from_status_field(field)? {
+                if let Some(submodule) = submodules
+                    .iter_mut()
+                    .find(|submodule| submodule.path == *path)
+                {
+                    submodule.change = change;
+                }
+            }
+        }
+        Ok(submodules)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Submodule, SubmoduleChange};
+
+    const RECORDED: &str = "d6b791fb7420c481a7fda55dae90634541d3f7fe";
+    const CHECKED_OUT: &str = "d6750800f7dc004246dae115fa3ea21f27b071f6";
+
+    #[test]
+    fn parses_status_fields() {
+        assert_eq!(SubmoduleChange::from_status_field("N...").unwrap(), None);
+        assert_eq!(
+            SubmoduleChange::from_status_field("SC.U").unwrap(),
+            Some(SubmoduleChange {
+                commit_changed: true,
+                modified_content: false,
+                untracked_content: true,
+            })
+        );
+        assert!(SubmoduleChange::from_status_field("SC").is_err());
+    }
+
+    #[test]
+    fn parses_submodules() {
+        let gitmodules = "submodule.libs/my lib.path\nlibs/my lib\0submodule.libs/my lib.url\n../lib\0submodule.libs/my lib.branch\nmain\0submodule.v1.0.path\nvendor\0";
+        let ls_files = format!(
+            "100644 9b16a439cb06cc30a93d02f27547f7c252258048 0\t.gitmodules\0160000 {RECORDED} 0\tlibs/my lib\0160000 {RECORDED} 0\tvendor\0"
+        );
+        let submodule_status =
+            format!("+{CHECKED_OUT} libs/my lib (heads/master)\n-{RECORDED} vendor\n");
+        let status = format!(
+            "1 .M SC.U 160000 160000 160000 {RECORDED} {RECORDED} libs/my lib\0? untracked file\0"
+        );
+
+        let submodules = Submodule::from_gitmodules(gitmodules);
+        let submodules = Submodule::with_recorded_commits(submodules, &ls_files).unwrap();
+        let submodules =
+            Submodule::with_checked_out_commits(submodules, &submodule_status).unwrap();
+        let submodules = Submodule::with_changes(submodules, &status).unwrap();
+
+        assert_eq!(
+            submodules,
+            vec![
+                Submodule {
+                    name: "libs/my lib".into(),
+                    path: "libs/my lib".into(),
+                    url: Some("../lib".into()),
+                    recorded_commit: Some(RECORDED.parse().unwrap()),
+                    checked_out_commit: Some(CHECKED_OUT.parse().unwrap()),
+                    change: SubmoduleChange {
+                        commit_changed: true,
+                        modified_content: false,
+                        untracked_content: true,
+                    },
+                },
+                Submodule {
+                    name: "v1.0".into(),
+                    path: "vendor".into(),
+                    url: None,
+                    recorded_commit: Some(RECORDED.parse().unwrap()),
+                    checked_out_commit: None,
+                    change: SubmoduleChange::default(),
+                },
+            ]
+        );
+    }
+}
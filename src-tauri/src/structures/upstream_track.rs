@@ -40,3 +40,10 @@ impl FromStr for UpstreamTrack {
         }
     }
 }
+
+impl UpstreamTrack {
+    /// Whether the branch tracks an upstream that no longer exists
+    pub fn is_gone(&self) -> bool {
+        self.0.is_none()
+    }
+}
@@ -0,0 +1,132 @@
+use serde::Serialize;
+use specta::Type;
+
+use super::hash::GitHash;
+
+#[derive(Debug, Serialize, Type, Clone, PartialEq, Eq)]
+pub struct Worktree {
+    /// Absolute path of the worktree
+    pub path: String,
+    /// The main worktree is the one the repository was cloned or initialized in
+    pub main: bool,
+    pub bare: bool,
+    /// None for a bare repository
+    pub head: Option<GitHash>,
+    /// e.g. `feat/implement-stuff`, None if HEAD is detached
+    pub branch: Option<String>,
+    /// Some with the reason, which can be empty, if the worktree is locked
+    pub locked: Option<String>,
+    /// Some with the reason if the worktree's folder is missing and `git worktree prune` would remove it
+    pub prunable: Option<String>,
+}
+
+impl Worktree {
+    fn new(path: &str, main: bool) -> Self {
+        Self {
+            path: path.into(),
+            main,
+            bare: false,
+            head: None,
+            branch: None,
+            locked: None,
+            prunable: None,
+        }
+    }
+
+    /// Parse the output of `git worktree list --porcelain`, where the main worktree comes first
+    pub fn from_porcelain(s: &str) -> Result<Vec<Self>, String> {
+        let mut worktrees: Vec<Self> = Vec::new();
+
+        for line in s.lines() {
+            let (attribute, value) = line.split_once(' ').unwrap_or((line, ""));
+            if attribute == "worktree" {
+                worktrees.push(Self::new(value, worktrees.is_empty()));
+                continue;
+            }
+
+            let Some(worktree) = worktrees.last_mut() else {
+                if line.is_empty() {
+                    continue;
+                }
+                return Err(format!("Expected a worktree path, got: {line}"));
+            };
+            match attribute {
+                "HEAD" => worktree.head = Some(value.parse()?),
+                "branch" => {
+                    worktree.branch =
+                        Some(value.strip_prefix("refs/heads/").unwrap_or(value).into())
+                }
+                "bare" => worktree.bare = true,
+                "locked" => worktree.locked = Some(value.into()),
+                "prunable" => worktree.prunable = Some(value.into()),
+                _ => {}
+            }
+        }
+
+        Ok(worktrees)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Worktree;
+
+    #[test]
+    fn parses_worktree_list() {
+        let hash = "681e1a630c25140f6c17ec98aedf762553592899";
+        let output = format!(
+            "worktree /repo\nHEAD {hash}\nbranch refs/heads/main\n\nworktree /repo-feat\nHEAD {hash}\nbranch refs/heads/feat/stuff\nlocked on usb drive\n\nworktree /tmp/review\nHEAD {hash}\ndetached\nlocked\nprunable gitdir file points to non-existent location\n\n"
+        );
+
+        assert_eq!(
+            Worktree::from_porcelain(&output).unwrap(),
+            vec![
+                Worktree {
+                    path: "/repo".into(),
+                    main: true,
+                    bare: false,
+                    head: Some(hash.parse().unwrap()),
+                    branch: Some("main".into()),
+                    locked: None,
+                    prunable: None,
+                },
+                Worktree {
+                    path: "/repo-feat".into(),
+                    main: false,
+                    bare: false,
+                    head: Some(hash.parse().unwrap()),
+                    branch: Some("feat/stuff".into()),
+                    locked: Some("on usb drive".into()),
+                    prunable: None,
+                },
+                Worktree {
+                    path: "/tmp/review".into(),
+                    main: false,
+                    bare: false,
+                    head: Some(hash.parse().unwrap()),
+                    branch: None,
+                    locked: Some("".into()),
+                    prunable: Some("gitdir file points to non-existent location".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_bare_main_worktree() {
+        let output = "worktree /repo.git\nbare\n\n";
+
+        assert_eq!(
+            Worktree::from_porcelain(output).unwrap(),
+            vec![Worktree {
+                path: "/repo.git".into(),
+                main: true,
+                bare: true,
+                head: None,
+                branch: None,
+                locked: None,
+                prunable: None,
+            }]
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use chardetng::{EncodingDetector, Iso2022JpDetection, Utf8Detection};
+use encoding_rs::{Encoding, UTF_8};
+
+/// Decode the raw contents of a file for display, normalising CRLF line endings to LF so
+/// that byte offsets into the decoded text line up with the lines git reports.
+///
+/// The encoding is taken from (in order of priority):
+/// - a byte order mark
+/// - utf8, if the contents are valid (git stores files with a `working-tree-encoding` as utf8)
+/// - `label`, e.g. from the `encoding` or `working-tree-encoding` git attributes
+/// - a guess based on the contents
+pub fn decode_file(bytes: &[u8], label: Option<&str>) -> String {
+    let encoding = Encoding::for_bom(bytes)
+        .map(|(encoding, _)| encoding)
+        .or_else(|| std::str::from_utf8(bytes).is_ok().then_some(UTF_8))
+        .or_else(|| label.and_then(|label| Encoding::for_label(label.as_bytes())))
+        .unwrap_or_else(|| {
+            let mut detector = EncodingDetector::new(Iso2022JpDetection::Deny);
+            detector.feed(bytes, true);
+            detector.guess(None, Utf8Detection::Allow)
+        });
+
+    let (text, _, _) = encoding.decode(bytes);
+    text.replace("\r\n", "\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::decode_file;
+
+    #[test]
+    fn decodes_utf8() {
+        assert_eq!(decode_file("héllo wörld".as_bytes(), None), "héllo wörld");
+    }
+
+    #[test]
+    fn decodes_latin1() {
+        let bytes = b"caf\xe9 cr\xe8me br\xfbl\xe9e, d\xe9j\xe0 vu, na\xefve fa\xe7ade";
+        assert_eq!(
+            decode_file(bytes, None),
+            "café crème brûlée, déjà vu, naïve façade"
+        );
+    }
+
+    #[test]
+    fn decodes_with_label() {
+        // "日本語" in Shift-JIS
+        let bytes = b"\x93\xfa\x96\x7b\x8c\xea";
+        assert_eq!(decode_file(bytes, Some("SHIFT-JIS")), "日本語");
+    }
+
+    #[test]
+    fn normalises_crlf() {
+        assert_eq!(
+            decode_file(b"first\r\nsecond\r\nthird", None),
+            "first\nsecond\nthird"
+        );
+    }
+}
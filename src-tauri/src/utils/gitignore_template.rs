@@ -0,0 +1,26 @@
+use serde::Deserialize;
+use specta::Type;
+
+/// Starter `.gitignore` files bundled with the app
+#[derive(Debug, Clone, Copy, Deserialize, Type)]
+pub enum GitignoreTemplate {
+    Rust,
+    Node,
+    Python,
+    Go,
+    Java,
+    MacOS,
+}
+
+impl GitignoreTemplate {
+    pub fn contents(&self) -> &'static str {
+        match self {
+            Self::Rust => include_str!("../../templates/gitignore/rust.gitignore"),
+            Self::Node => include_str!("../../templates/gitignore/node.gitignore"),
+            Self::Python => include_str!("../../templates/gitignore/python.gitignore"),
+            Self::Go => include_str!("../../templates/gitignore/go.gitignore"),
+            Self::Java => include_str!("../../templates/gitignore/java.gitignore"),
+            Self::MacOS => include_str!("../../templates/gitignore/macos.gitignore"),
+        }
+    }
+}
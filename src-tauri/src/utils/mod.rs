@@ -1 +1,4 @@
+pub mod encoding;
+pub mod gitignore_template;
 pub mod highlight;
+pub mod mime;
@@ -0,0 +1,394 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use ignore::{
+    gitignore::{Gitignore, GitignoreBuilder},
+    WalkBuilder,
+};
+use notify_debouncer_mini::{
+    new_debouncer,
+    notify::{self, RecommendedWatcher, RecursiveMode, Watcher},
+    Debouncer,
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri_specta::Event;
+
+use crate::db::Repository;
+
+/// Changes within this time are combined into a single event
+const DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(300);
+
+/// Emitted when files in the work tree or the index change
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct WorkingTreeChangedEvent {
+    pub repository_id: i64,
+}
+
+/// Emitted when branches or tags are created, deleted or moved
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct RefsChangedEvent {
+    pub repository_id: i64,
+}
+
+/// Emitted when a different branch or commit is checked out
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct HeadMovedEvent {
+    pub repository_id: i64,
+}
+
+/// Emitted when changes to the open repository can no longer be detected, e.g. because the
+/// system's limit on watched folders was reached
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+pub struct RepositoryWatchFailedEvent {
+    pub repository_id: i64,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum RepositoryChange {
+    WorkingTree,
+    Refs,
+    Head,
+}
+
+/// Watches the open repository for changes made outside the app
+#[derive(Default)]
+pub struct RepositoryWatcher {
+    debouncer: Mutex<Option<Arc<Mutex<Debouncer<RecommendedWatcher>>>>>,
+    /// Why the open repository isn't being watched, kept so it can be shown once the frontend loads
+    failure: Arc<Mutex<Option<RepositoryWatchFailedEvent>>>,
+}
+
+impl RepositoryWatcher {
+    /// Stop watching the previous repository and start watching `repository`
+    pub fn watch(
+        &self,
+        app_handle: &tauri::AppHandle,
+        repository: Option<&Repository>,
+    ) -> notify::Result<()> {
+        // Dropping the debouncer stops the watcher and the thread handling its events
+        drop(self.debouncer.lock().unwrap().take());
+        *self.failure.lock().unwrap() = None;
+
+        let Some(repository) = repository else {
+            return Ok(());
+        };
+
+        let result = self.start(app_handle, repository);
+        if let Err(err) = &result {
+            *self.failure.lock().unwrap() = Some(RepositoryWatchFailedEvent {
+                repository_id: repository.id,
+                message: err.to_string(),
+            });
+        }
+        result
+    }
+
+    pub fn failure(&self) -> Option<RepositoryWatchFailedEvent> {
+        self.failure.lock().unwrap().clone()
+    }
+
+    fn start(&self, app_handle: &tauri::AppHandle, repository: &Repository) -> notify::Result<()> {
+        let repository_id = repository.id;
+        let work_tree = PathBuf::from(&repository.local_path);
+        let git_dir = git_dir(&work_tree);
+        let common_dir = common_dir(&git_dir);
+        let mut gitignore = build_gitignore(&work_tree, &git_dir);
+        let app_handle = app_handle.clone();
+        let failure = self.failure.clone();
+
+        let (sender, receiver) = mpsc::channel();
+        let mut debouncer = new_debouncer(DEBOUNCE_TIMEOUT, sender)?;
+        let mut watched = HashSet::new();
+        watch_folders(debouncer.watcher(), &work_tree, &mut watched)?;
+        // Only the folders holding HEAD, the index and refs are watched, as `objects` can hold
+        // thousands of folders. Git replaces these files instead of writing to them, so their
+        // folders are watched rather than the files themselves.
+        debouncer
+            .watcher()
+            .watch(&git_dir, RecursiveMode::NonRecursive)?;
+        // Refs are shared by all worktrees, so they're in the common git directory
+        if common_dir != git_dir {
+            debouncer
+                .watcher()
+                .watch(&common_dir, RecursiveMode::NonRecursive)?;
+        }
+        let refs_dir = common_dir.join("refs");
+        if refs_dir.is_dir() {
+            debouncer
+                .watcher()
+                .watch(&refs_dir, RecursiveMode::Recursive)?;
+        }
+        let debouncer = Arc::new(Mutex::new(debouncer));
+
+        // The thread stops when the debouncer is dropped, as that closes the channel
+        let weak_debouncer = Arc::downgrade(&debouncer);
+        thread::spawn(move || {
+            let report = |err: notify::Error| {
+                let event = RepositoryWatchFailedEvent {
+                    repository_id,
+                    message: err.to_string(),
+                };
+                *failure.lock().unwrap() = Some(event.clone());
+                event.emit(&app_handle).expect("Failed to emit event");
+            };
+
+            for result in receiver {
+                let events = match result {
+                    Ok(events) => events,
+                    Err(err) => {
+                        report(err);
+                        continue;
+                    }
+                };
+
+                if events
+                    .iter()
+                    .any(|event| event.path.ends_with(".gitignore"))
+                {
+                    gitignore = build_gitignore(&work_tree, &git_dir);
+                }
+
+                let mut changes = HashSet::new();
+                let mut new_folders = Vec::new();
+                for event in events.iter() {
+                    let change =
+                        classify(&event.path, &work_tree, &git_dir, &common_dir, &gitignore);
+                    if change == Some(RepositoryChange::WorkingTree)
+                        && event.path.is_dir()
+                        && !watched.contains(&event.path)
+                    {
+                        new_folders.push(&event.path);
+                    }
+                    if !event.path.exists() {
+                        watched.retain(|path| !path.starts_with(&event.path));
+                    }
+                    changes.extend(change);
+                }
+
+                // Folders aren't watched recursively, so watch ones created since watching started
+                if let Some(debouncer) = weak_debouncer.upgrade() {
+                    let mut debouncer = debouncer.lock().unwrap();
+                    for folder in new_folders {
+                        if let Err(err) = watch_folders(debouncer.watcher(), folder, &mut watched) {
+                            report(err);
+                        }
+                    }
+                }
+
+                for change in changes {
+                    match change {
+                        RepositoryChange::WorkingTree => {
+                            WorkingTreeChangedEvent { repository_id }.emit(&app_handle)
+                        }
+                        RepositoryChange::Refs => {
+                            RefsChangedEvent { repository_id }.emit(&app_handle)
+                        }
+                        RepositoryChange::Head => {
+                            HeadMovedEvent { repository_id }.emit(&app_handle)
+                        }
+                    }
+                    .expect("Failed to emit event");
+                }
+            }
+        });
+
+        *self.debouncer.lock().unwrap() = Some(debouncer);
+
+        Ok(())
+    }
+}
+
+/// Watch `folder` and the folders in it that aren't ignored, each on its own, so ignored folders
+/// like `node_modules` don't use up the system's limit on watches
+fn watch_folders(
+    watcher: &mut dyn Watcher,
+    folder: &Path,
+    watched: &mut HashSet<PathBuf>,
+) -> notify::Result<()> {
+    let walk = WalkBuilder::new(folder)
+        .hidden(false)
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .build();
+    for entry in walk.flatten() {
+        if entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_dir())
+            && watched.insert(entry.path().to_path_buf())
+        {
+            watcher.watch(entry.path(), RecursiveMode::NonRecursive)?;
+        }
+    }
+    Ok(())
+}
+
+/// Find the git directory of a work tree, following a `.git` file if there is one
+fn git_dir(work_tree: &Path) -> PathBuf {
+    let dot_git = work_tree.join(".git");
+    std::fs::read_to_string(&dot_git)
+        .ok()
+        .and_then(|contents| {
+            contents
+                .trim()
+                .strip_prefix("gitdir: ")
+                .map(|path| work_tree.join(path))
+        })
+        .unwrap_or(dot_git)
+}
+
+/// Find the git directory shared by all worktrees, like `git rev-parse --git-common-dir`
+fn common_dir(git_dir: &Path) -> PathBuf {
+    // Usually a relative path like `../..`, which wouldn't match the paths of changed files
+    std::fs::read_to_string(git_dir.join("commondir"))
+        .and_then(|path| git_dir.join(path.trim()).canonicalize())
+        .unwrap_or(git_dir.into())
+}
+
+/// Only the top level `.gitignore` and `info/exclude` are used, so some ignored files in
+/// subdirectories may still cause a (harmless) refresh
+fn build_gitignore(work_tree: &Path, git_dir: &Path) -> Gitignore {
+    let mut builder = GitignoreBuilder::new(work_tree);
+    builder.add(work_tree.join(".gitignore"));
+    builder.add(git_dir.join("info").join("exclude"));
+    builder.build().unwrap_or_else(|_| Gitignore::empty())
+}
+
+fn classify(
+    path: &Path,
+    work_tree: &Path,
+    git_dir: &Path,
+    common_dir: &Path,
+    gitignore: &Gitignore,
+) -> Option<RepositoryChange> {
+    let is_ref = |path: &str| {
+        path == "packed-refs" || (path.starts_with("refs") && !path.ends_with(".lock"))
+    };
+    if let Ok(path) = path.strip_prefix(git_dir) {
+        let path = path.to_string_lossy();
+        return match path.as_ref() {
+            "HEAD" => Some(RepositoryChange::Head),
+            "index" => Some(RepositoryChange::WorkingTree),
+            path if is_ref(path) => Some(RepositoryChange::Refs),
+            // Objects, logs and lock files change along with the files above
+            _ => None,
+        };
+    }
+    if let Ok(path) = path.strip_prefix(common_dir) {
+        // Other worktrees' HEAD and index are in here too, which don't affect this one
+        return is_ref(&path.to_string_lossy()).then_some(RepositoryChange::Refs);
+    }
+
+    let relative_path = path.strip_prefix(work_tree).ok()?;
+    let is_dir = path.is_dir();
+    if gitignore
+        .matched_path_or_any_parents(relative_path, is_dir)
+        .is_ignore()
+    {
+        return None;
+    }
+
+    Some(RepositoryChange::WorkingTree)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashSet, fs, path::Path};
+
+    use ignore::gitignore::GitignoreBuilder;
+    use notify_debouncer_mini::notify::NullWatcher;
+
+    use super::{classify, watch_folders, RepositoryChange};
+
+    #[test]
+    fn classifies_changed_paths() {
+        let work_tree = Path::new("/repo");
+        let git_dir = Path::new("/repo/.git");
+        let mut builder = GitignoreBuilder::new(work_tree);
+        builder.add_line(None, "target/").unwrap();
+        builder.add_line(None, "*.log").unwrap();
+        let gitignore = builder.build().unwrap();
+
+        let classify =
+            |path: &str| classify(Path::new(path), work_tree, git_dir, git_dir, &gitignore);
+
+        assert_eq!(classify("/repo/.git/HEAD"), Some(RepositoryChange::Head));
+        assert_eq!(
+            classify("/repo/.git/refs/heads/main"),
+            Some(RepositoryChange::Refs)
+        );
+        assert_eq!(classify("/repo/.git/refs/heads/main.lock"), None);
+        assert_eq!(
+            classify("/repo/.git/index"),
+            Some(RepositoryChange::WorkingTree)
+        );
+        assert_eq!(classify("/repo/.git/objects/ab/cdef"), None);
+        assert_eq!(
+            classify("/repo/src/main.rs"),
+            Some(RepositoryChange::WorkingTree)
+        );
+        assert_eq!(classify("/repo/target/debug/strand"), None);
+        assert_eq!(classify("/repo/debug.log"), None);
+    }
+
+    #[test]
+    fn classifies_linked_worktree_paths() {
+        let work_tree = Path::new("/repo-feat");
+        let common_dir = Path::new("/repo/.git");
+        let git_dir = Path::new("/repo/.git/worktrees/repo-feat");
+        let gitignore = GitignoreBuilder::new(work_tree).build().unwrap();
+
+        let classify =
+            |path: &str| classify(Path::new(path), work_tree, git_dir, common_dir, &gitignore);
+
+        assert_eq!(
+            classify("/repo/.git/worktrees/repo-feat/HEAD"),
+            Some(RepositoryChange::Head)
+        );
+        assert_eq!(
+            classify("/repo/.git/refs/heads/feat"),
+            Some(RepositoryChange::Refs)
+        );
+        assert_eq!(
+            classify("/repo/.git/packed-refs"),
+            Some(RepositoryChange::Refs)
+        );
+        assert_eq!(classify("/repo/.git/HEAD"), None);
+        assert_eq!(classify("/repo/.git/index"), None);
+        assert_eq!(classify("/repo/.git/worktrees/other/HEAD"), None);
+    }
+
+    #[test]
+    fn skips_ignored_folders() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for folder in [
+            ".git/refs",
+            "src/nested",
+            "target/debug",
+            "web/node_modules/x",
+        ] {
+            fs::create_dir_all(root.join(folder)).unwrap();
+        }
+        fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        fs::write(root.join("web/.gitignore"), "node_modules/\n").unwrap();
+
+        let mut watched = HashSet::new();
+        watch_folders(&mut NullWatcher, root, &mut watched).unwrap();
+
+        assert_eq!(
+            watched,
+            HashSet::from([
+                root.into(),
+                root.join("src"),
+                root.join("src/nested"),
+                root.join("web"),
+            ])
+        );
+    }
+}
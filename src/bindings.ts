@@ -13,6 +13,31 @@ async getBranches() : Promise<Result<Branches, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Preview the local branches merged into `base` or whose upstream is gone.
+ * Branches that are checked out or match a protected name are never included.
+ */
+async getBranchCleanupCandidates(base: string) : Promise<Result<BranchCleanupCandidate[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_branch_cleanup_candidates", { base }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Delete the confirmed local branches, refusing if any of them is checked out, protected, or
+ * neither merged into `base` nor gone from its remote. The candidates are checked again, as the
+ * branches may have changed since the preview.
+ */
+async deleteBranches(branches: string[], base: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_branches", { branches, base }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async addRepositoryFromPath(localPath: string) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("add_repository_from_path", { localPath }) };
@@ -21,6 +46,28 @@ async addRepositoryFromPath(localPath: string) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Clone `source` (a URL or local path) into the new folder `destination`, then add and open it
+ */
+async cloneRepository(source: string, destination: string, options: CloneOptions) : Promise<Result<Repository, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("clone_repository", { source, destination, options }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Create a new repository in `local_path`, creating the folder if needed, then add and open it
+ */
+async initRepository(localPath: string, options: InitOptions) : Promise<Result<Repository, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("init_repository", { localPath, options }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async setOpenRepository(id: number | null) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("set_open_repository", { id }) };
@@ -45,160 +92,1170 @@ async getRepositories() : Promise<Result<Repository[], string>> {
     else return { status: "error", error: e  as any };
 }
 },
-async gitFetch() : Promise<Result<null, string>> {
+/**
+ * Get why the open repository isn't being watched for changes, if it isn't
+ */
+async getRepositoryWatchFailure() : Promise<Result<RepositoryWatchFailedEvent | null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("git_fetch") };
+    return { status: "ok", data: await TAURI_INVOKE("get_repository_watch_failure") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getGitCommandLog(filter: GitCommandType | null) : Promise<Result<GitCommandLog[], string>> {
+/**
+ * Forget a repository and its command log. The files on disk aren't touched.
+ */
+async removeRepository(id: number) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_git_command_log", { filter }) };
+    return { status: "ok", data: await TAURI_INVOKE("remove_repository", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getGraph() : Promise<Result<Commit[], string>> {
+/**
+ * Change the name a repository is shown with
+ */
+async renameRepository(id: number, name: string) : Promise<Result<Repository, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_graph") };
+    return { status: "ok", data: await TAURI_INVOKE("rename_repository", { id, name }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getCommitFiles(commitHash: GitHash) : Promise<Result<File[], string>> {
+/**
+ * Update the path of a repository that was moved on disk
+ */
+async relocateRepository(id: number, localPath: string) : Promise<Result<Repository, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_commit_files", { commitHash }) };
+    return { status: "ok", data: await TAURI_INVOKE("relocate_repository", { id, localPath }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getFileDiff(commitHash: GitHash, path: string) : Promise<Result<FileDiff, string>> {
+/**
+ * Pinned repositories are listed before the others
+ */
+async setRepositoryPinned(id: number, pinned: boolean) : Promise<Result<Repository, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_file_diff", { commitHash, path }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_repository_pinned", { id, pinned }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getChangedFiles() : Promise<Result<[File[], File[]], string>> {
+/**
+ * Set the order repositories are listed in, within pinned and unpinned repositories
+ */
+async reorderRepositories(ids: number[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_changed_files") };
+    return { status: "ok", data: await TAURI_INVOKE("reorder_repositories", { ids }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
+},
+async gitFetch() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("git_fetch") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
 }
+},
+async getRemotes() : Promise<Result<Remote[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_remotes") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
 }
-
-/** user-defined events **/
-
-
-export const events = __makeEvents__<{
-gitCommandEvent: GitCommandEvent
-}>({
-gitCommandEvent: "git-command-event"
-})
-
-/** user-defined constants **/
-
-
-
-/** user-defined types **/
-
-export type Branches = { local: LocalBranch[]; remote: RemoteBranch[] }
-export type Commit = { hash: GitHash; parent_hashes: GitHash[]; author: CommitUser; committer: CommitUser; message: string; description: string | null }
-export type CommitUser = { name: string; email: string; date: string; email_hash: string }
-export type DiffHunk = { 
+},
 /**
- * Raw header text, or None if the whole file was requested
+ * Add a remote, fetching its branches straight away if `fetch` is set
  */
-header: string | null; lines: LineDiff[] }
-export type DiffStatus = "Added" | "Removed" | "Unmodified"
-export type File = { 
+async addRemote(name: string, url: string, fetch: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_remote", { name, url, fetch }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * None if status is addition or unmerged
+ * Rename a remote, along with its remote-tracking branches and upstream config
  */
-src_hash: GitHash | null; 
+async renameRemote(oldName: string, newName: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("rename_remote", { oldName, newName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * None if status is deletion, unmerged or "work tree out of sync with the index"
+ * Remove a remote and its remote-tracking branches
  */
-dst_hash: GitHash | null; status: FileStatus; 
+async removeRemote(name: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("remove_remote", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Optional similarity percentage (0..100) if status is copied, renamed, or modified
+ * Replace the URL of a remote. If `push` is set, only the push URL is changed.
  */
-score: number | null; src_path: string; 
+async setRemoteUrl(name: string, url: string, push: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_remote_url", { name, url, push }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Optional destination path if status is copied or renamed
+ * Delete remote-tracking branches whose branch no longer exists on the remote
  */
-dst_path: string | null }
-export type FileDiff = DiffHunk[]
-export type FileStatus = 
+async pruneRemote(name: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("prune_remote", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Addition of a file
+ * Fetch a single remote, unlike `git_fetch` which fetches all of them
  */
-"Added" | 
+async fetchRemote(name: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_remote", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getWorktrees() : Promise<Result<Worktree[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_worktrees") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Copy of a file into a new one
+ * Check out `branch` in a new worktree at `path`, which is relative to the open repository
  */
-"Copied" | 
+async addWorktree(path: string, branch: string, options: AddWorktreeOptions) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_worktree", { path, branch, options }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Deletion of a file
+ * Delete a linked worktree's folder, and remove it from the repository list if it was added.
+ * Without `force`, fails if the worktree has uncommitted changes or is locked.
  */
-"Deleted" | 
+async removeWorktree(path: string, force: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("remove_worktree", { path, force }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Modification of the contents or mode of a file
+ * Prevent a linked worktree from being pruned, moved or removed, e.g. while it's on a removable drive
  */
-"Modified" | 
+async lockWorktree(path: string, reason: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("lock_worktree", { path, reason }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async unlockWorktree(path: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("unlock_worktree", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Renaming of a file
+ * Clean up the administrative files of worktrees whose folders were deleted
  */
-"Renamed" | 
+async pruneWorktrees() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("prune_worktrees") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Change in the type of the file (regular file, symbolic link or submodule)
+ * Add a worktree to the repository list without opening it, linked to its main repository
  */
-"TypeChanged" | 
+async registerWorktree(path: string) : Promise<Result<Repository, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("register_worktree", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * File is unmerged (you must complete the merge before it can be committed)
+ * List the submodules configured in `.gitmodules` with their recorded and checked out commits
  */
-"Unmerged" | 
+async getSubmodules() : Promise<Result<Submodule[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_submodules") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * "Unknown" change type
+ * Copy the URLs of submodules from `.gitmodules` into the repository's config, all of them if
+ * `paths` is empty
  */
-"Unknown"
-export type Fragment = { text: string; status: DiffStatus; class: string[] | null }
-export type GitCommandEvent = GitCommandLog
-export type GitCommandLog = { id: number; command: string; command_type: GitCommandType; created_at: string }
-export type GitCommandType = "Query" | "Mutation"
-export type GitHash = string
-export type LineDiff = { fragments: Fragment[]; status: DiffStatus; 
+async initSubmodules(paths: string[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("init_submodules", { paths }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * None if status is Added
+ * Clone missing submodules and check out their recorded commits, all of them if `paths` is empty
  */
-src_line_number: number | null; 
+async updateSubmodules(paths: string[], options: UpdateSubmodulesOptions) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_submodules", { paths, options }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * None if status is Removed
+ * Update the remote URLs of submodules after they change in `.gitmodules`, all of them if
+ * `paths` is empty
  */
-dst_line_number: number | null }
-export type LocalBranch = { head: boolean; 
+async syncSubmodules(paths: string[], recursive: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("sync_submodules", { paths, recursive }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * e.g. `["feat", "implement-stuff"]`
+ * Remove the working trees and config of submodules, keeping them in `.gitmodules`.
+ * Without `force`, fails if a submodule has local changes.
  */
-name: string[]; upstream_name: string[]; upstream_track: UpstreamTrack; hash: GitHash }
-export type RemoteBranch = { 
+async deinitSubmodules(paths: string[], force: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("deinit_submodules", { paths, force }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * e.g. `["origin", "feat", "implement-stuff"]`
+ * Get a page of the command log, newest first. Pass the previous page's `next_cursor` to continue.
  */
-name: string[]; hash: GitHash }
-export type Repository = { id: number; name: string; local_path: string; created_at: string; last_opened_at: string | null; last_fetched_at: string | null; has_changes: boolean }
+async getGitCommandLog(filter: GitCommandType | null, search: string | null, cursor: GitCommandLogCursor | null, limit: number) : Promise<Result<GitCommandLogPage, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_git_command_log", { filter, search, cursor, limit }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * If both are 0, it's in sync. If None, the tracked upstream is missing.
+ * Set how many commands of each type to keep in a repository's log
  */
-export type UpstreamTrack = [number, number] | null
+async setGitCommandLogRetention(repositoryId: number, retention: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_git_command_log_retention", { repositoryId, retention }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Write the open repository's full command log to `path`
+ */
+async exportGitCommandLog(format: GitCommandLogExportFormat, path: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_git_command_log", { format, path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getGraph() : Promise<Result<Commit[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_graph") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getCommitFiles(commitHash: GitHash, mergeDiffMode: MergeDiffMode) : Promise<Result<CommitFiles, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_commit_files", { commitHash, mergeDiffMode }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getFileDiff(revisions: DiffRevisions, path: string, layout: DiffLayout) : Promise<Result<FileDiff, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_file_diff", { revisions, path, layout }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Compare two revisions, which can be anything git can resolve to a commit (e.g. branch names)
+ */
+async compareRevisions(base: string, head: string, mode: CompareMode) : Promise<Result<RevisionComparison, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("compare_revisions", { base, head, mode }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Write a patch file for each commit into `directory`, returning the paths of the created files
+ */
+async exportPatches(revisions: PatchRevisions, directory: string) : Promise<Result<string[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_patches", { revisions, directory }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Create a unified diff of changes in the working tree, or in the index if `staged`.
+ * Only includes the given paths, or all changes if `paths` is empty.
+ */
+async createPatch(staged: boolean, paths: string[]) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_patch", { staged, paths }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Check whether a patch or mbox file applies cleanly to the working tree, without changing anything
+ */
+async checkPatch(path: string) : Promise<Result<PatchCheck, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_patch", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Apply a patch or mbox file. Check it first with `check_patch` to see why it might fail.
+ */
+async applyPatch(path: string, method: PatchApplyMethod) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("apply_patch", { path, method }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getRunningGitCommands() : Promise<Result<RunningGitCommand[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_running_git_commands") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async cancelGitCommand(id: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("cancel_git_command", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getChangedFiles() : Promise<Result<[File[], File[]], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_changed_files") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getSettings() : Promise<Result<Settings, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_settings") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Replace all settings and check git again, emitting a [`SettingsChangedEvent`]
+ */
+async updateSettings(settings: Settings) : Promise<Result<Settings, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_settings", { settings }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get the result of checking the git executable, which is done at startup and when settings change
+ */
+async getGitVersion() : Promise<Result<GitVersionCheck, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_git_version") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+}
+}
+
+/** user-defined events **/
+
+
+export const events = __makeEvents__<{
+gitCommandEvent: GitCommandEvent,
+gitProgressEvent: GitProgressEvent,
+headMovedEvent: HeadMovedEvent,
+refsChangedEvent: RefsChangedEvent,
+repositoryFetchedEvent: RepositoryFetchedEvent,
+repositoryStatusEvent: RepositoryStatusEvent,
+repositoryWatchFailedEvent: RepositoryWatchFailedEvent,
+settingsChangedEvent: SettingsChangedEvent,
+workingTreeChangedEvent: WorkingTreeChangedEvent
+}>({
+gitCommandEvent: "git-command-event",
+gitProgressEvent: "git-progress-event",
+headMovedEvent: "head-moved-event",
+refsChangedEvent: "refs-changed-event",
+repositoryFetchedEvent: "repository-fetched-event",
+repositoryStatusEvent: "repository-status-event",
+repositoryWatchFailedEvent: "repository-watch-failed-event",
+settingsChangedEvent: "settings-changed-event",
+workingTreeChangedEvent: "working-tree-changed-event"
+})
+
+/** user-defined constants **/
+
+
+
+/** user-defined types **/
+
+export type AddWorktreeOptions = { 
+/**
+ * Create `branch` instead of checking out an existing one
+ */
+create_branch: boolean; 
+/**
+ * Where the new branch starts, defaults to HEAD
+ */
+start_point: string | null; 
+/**
+ * Also add the worktree to the repository list
+ */
+register: boolean }
+export type BinaryFile = { 
+/**
+ * Size in bytes
+ */
+size: number; mime_type: string }
+export type BranchCleanupCandidate = { 
+/**
+ * Full branch name, e.g. `feat/implement-stuff`
+ */
+name: string; reasons: BranchCleanupReason[]; hash: GitHash; last_commit_author: string; last_commit_date: string; last_commit_message: string }
+export type BranchCleanupReason = 
+/**
+ * Fully merged into the chosen base
+ */
+"Merged" | 
+/**
+ * The upstream branch was deleted from the remote
+ */
+"UpstreamGone"
+export type Branches = { local: LocalBranch[]; remote: RemoteBranch[] }
+export type CloneOptions = { 
+/**
+ * Branch to check out instead of the remote's HEAD
+ */
+branch: string | null; 
+/**
+ * Only fetch this many commits of history
+ */
+depth: number | null; 
+/**
+ * Only fetch the history of the checked out branch
+ */
+single_branch: boolean; recurse_submodules: boolean; 
+/**
+ * Partial clone filter, e.g. `blob:none` to fetch file contents only when they're needed
+ */
+filter: string | null }
+export type CombinedDiffHunk = { 
+/**
+ * Raw header text
+ */
+header: string; lines: CombinedLineDiff[] }
+export type CombinedLineDiff = { fragments: Fragment[]; 
+/**
+ * Status of the line compared to each parent
+ */
+statuses: DiffStatus[]; 
+/**
+ * Line number in each parent, None if the line isn't in that parent
+ */
+src_line_numbers: (number | null)[]; 
+/**
+ * None if the line was removed from the result
+ */
+dst_line_number: number | null }
+export type Commit = { hash: GitHash; parent_hashes: GitHash[]; author: CommitUser; committer: CommitUser; message: string; description: string | null }
+export type CommitFiles = { files: File[]; 
+/**
+ * None if line counts aren't available, e.g. for combined diffs
+ */
+stats: DiffStats | null }
+export type CommitUser = { name: string; email: string; date: string; email_hash: string }
+export type CompareMode = 
+/**
+ * Changes between the two revisions directly (`base..head`)
+ */
+"TwoDot" | 
+/**
+ * Changes on head since it diverged from base (`base...head`)
+ */
+"ThreeDot"
+export type DiffHunk = { 
+/**
+ * Raw header text, or None if the whole file was requested
+ */
+header: string | null; lines: LineDiff[] }
+export type DiffLayout = 
+/**
+ * Removed and added lines one after the other
+ */
+"Unified" | 
+/**
+ * Removed and added lines side by side
+ */
+"Split"
+/**
+ * The revisions to diff a file between
+ */
+export type DiffRevisions = 
+/**
+ * Changes made in a single commit
+ */
+{ Commit: { hash: GitHash; merge_diff_mode: MergeDiffMode } } | 
+/**
+ * Changes between two commits, e.g. from a revision comparison
+ */
+{ Range: { src_hash: GitHash; dst_hash: GitHash } }
+/**
+ * Total lines changed across all files in a diff
+ */
+export type DiffStats = { files_changed: number; insertions: number; deletions: number }
+export type DiffStatus = "Added" | "Removed" | "Unmodified"
+export type File = { 
+/**
+ * None if status is addition or unmerged
+ */
+src_hash: GitHash | null; 
+/**
+ * None if status is deletion, unmerged or "work tree out of sync with the index"
+ */
+dst_hash: GitHash | null; status: FileStatus; 
+/**
+ * Optional similarity percentage (0..100) if status is copied, renamed, or modified
+ */
+score: number | null; src_path: string; 
+/**
+ * Optional destination path if status is copied or renamed
+ */
+dst_path: string | null; 
+/**
+ * None if line counts aren't available, e.g. for untracked files or combined diffs
+ */
+stats: FileStats | null; 
+/**
+ * Some if the file is a submodule, in which case the hashes are the commits it moved between
+ */
+submodule: SubmoduleChange | null }
+export type FileDiff = { Text: DiffHunk[] } | 
+/**
+ * A text diff with removed and added lines aligned side by side
+ */
+{ Split: SplitDiffHunk[] } | 
+/**
+ * A text diff of a merge commit against all of its parents
+ */
+{ Combined: CombinedDiffHunk[] } | 
+/**
+ * Git considers the file binary, so there is no text diff
+ */
+{ Binary: { 
+/**
+ * None if the file was added
+ */
+src: BinaryFile | null; 
+/**
+ * None if the file was deleted
+ */
+dst: BinaryFile | null } } | 
+/**
+ * A binary file that can be displayed as an image
+ */
+{ Image: { 
+/**
+ * None if the file was added
+ */
+src: ImageFile | null; 
+/**
+ * None if the file was deleted
+ */
+dst: ImageFile | null } } | 
+/**
+ * A submodule that moved from one commit to another
+ */
+{ Submodule: { 
+/**
+ * None if the submodule was added
+ */
+src: GitHash | null; 
+/**
+ * None if the submodule was removed
+ */
+dst: GitHash | null } }
+/**
+ * Number of lines changed in a file
+ */
+export type FileStats = { insertions: number; deletions: number; 
+/**
+ * Binary files have no line counts
+ */
+binary: boolean }
+export type FileStatus = 
+/**
+ * Addition of a file
+ */
+"Added" | 
+/**
+ * Copy of a file into a new one
+ */
+"Copied" | 
+/**
+ * Deletion of a file
+ */
+"Deleted" | 
+/**
+ * Modification of the contents or mode of a file
+ */
+"Modified" | 
+/**
+ * Renaming of a file
+ */
+"Renamed" | 
+/**
+ * Change in the type of the file (regular file, symbolic link or submodule)
+ */
+"TypeChanged" | 
+/**
+ * File is unmerged (you must complete the merge before it can be committed)
+ */
+"Unmerged" | 
+/**
+ * "Unknown" change type
+ */
+"Unknown"
+export type Fragment = { text: string; status: DiffStatus; class: string[] | null }
+/**
+ * Arguments passed to git, starting with the command.
+ * None for commands logged before these were recorded.
+ */
+export type GitCommandArgs = string[] | null
+export type GitCommandEvent = GitCommandLog
+export type GitCommandLog = { id: number; command: string; args: GitCommandArgs; command_type: GitCommandType; created_at: string; 
+/**
+ * Final progress of each phase, one per line, if the command reported progress
+ */
+progress: string | null; outcome: GitCommandOutcome; 
+/**
+ * None if git didn't exit by itself, e.g. it was cancelled
+ */
+exit_code: number | null; duration_ms: number; 
+/**
+ * None for commands logged before this was recorded
+ */
+working_directory: string | null; 
+/**
+ * Truncated to [`MAX_LOG_OUTPUT_LEN`]
+ */
+stdout: string | null; 
+/**
+ * Truncated to [`MAX_LOG_OUTPUT_LEN`]
+ */
+stderr: string | null }
+/**
+ * Position in the command log to continue a page from
+ */
+export type GitCommandLogCursor = { created_at: string; id: number }
+export type GitCommandLogExportFormat = "Json" | 
+/**
+ * Commands in the order they were run, with their results as comments
+ */
+"ShellScript"
+/**
+ * Entries of the command log, newest first
+ */
+export type GitCommandLogPage = { entries: GitCommandLog[]; 
+/**
+ * None if there are no older entries
+ */
+next_cursor: GitCommandLogCursor | null }
+/**
+ * How a logged command finished
+ */
+export type GitCommandOutcome = "Success" | "Cancelled" | "TimedOut" | 
+/**
+ * Git exited with an error
+ */
+"Failed"
+export type GitCommandType = "Query" | "Mutation"
+export type GitConfigOverride = { 
+/**
+ * e.g. `core.autocrlf`
+ */
+key: string; value: string }
+export type GitEnvironmentVariable = { name: string; value: string }
+export type GitHash = string
+/**
+ * A progress line reported by git on stderr, e.g. `Receiving objects:  45% (450/1000)`
+ */
+export type GitProgress = { 
+/**
+ * e.g. "Counting objects" or "Resolving deltas"
+ */
+phase: string; percentage: number | null; 
+/**
+ * Number of objects processed so far
+ */
+current: number | null; 
+/**
+ * None if git doesn't know the total yet
+ */
+total: number | null }
+/**
+ * Emitted while a command started with [`GitCommand::progress`] is running
+ */
+export type GitProgressEvent = { command_id: number; progress: GitProgress }
+/**
+ * Fields are ordered so versions compare correctly
+ */
+export type GitVersion = { major: number; minor: number; patch: number }
+/**
+ * Result of checking that the configured git executable can be used
+ */
+export type GitVersionCheck = { executable: string; 
+/**
+ * None if git couldn't be run or the version couldn't be parsed
+ */
+version: GitVersion | null; minimum_version: GitVersion; 
+/**
+ * Why git can't be used, if it can't
+ */
+error: string | null }
+/**
+ * Starter `.gitignore` files bundled with the app
+ */
+export type GitignoreTemplate = "Rust" | "Node" | "Python" | "Go" | "Java" | "MacOS"
+/**
+ * Emitted when a different branch or commit is checked out
+ */
+export type HeadMovedEvent = { repository_id: number }
+export type ImageFile = { 
+/**
+ * Size in bytes
+ */
+size: number; mime_type: string; 
+/**
+ * Contents of the image encoded as base64
+ */
+base64: string }
+export type InitOptions = { 
+/**
+ * e.g. `main`
+ */
+initial_branch: string; gitignore: GitignoreTemplate | null; 
+/**
+ * Create a README.md with the folder name as a heading
+ */
+readme: boolean; 
+/**
+ * Commit the starter files, or an empty commit if there aren't any
+ */
+initial_commit: boolean }
+export type LineDiff = { fragments: Fragment[]; status: DiffStatus; 
+/**
+ * None if status is Added
+ */
+src_line_number: number | null; 
+/**
+ * None if status is Removed
+ */
+dst_line_number: number | null; 
+/**
+ * Set if this line is part of a block that was moved
+ */
+moved: MovedLine | null }
+export type LocalBranch = { head: boolean; 
+/**
+ * e.g. `["feat", "implement-stuff"]`
+ */
+name: string[]; upstream_name: string[]; upstream_track: UpstreamTrack; hash: GitHash; 
+/**
+ * Path of the worktree the branch is checked out in, which is the open one when `head`
+ */
+worktree_path: string | null }
+/**
+ * Which changes to show for a merge commit. Has no effect on commits with a single parent.
+ */
+export type MergeDiffMode = 
+/**
+ * Changes compared to a single parent, starting from 1
+ */
+{ Parent: number } | 
+/**
+ * Changes compared to all parents at once, only for files that differ from every parent (`-c`)
+ */
+"Combined" | 
+/**
+ * Like combined, but also hides hunks that match one of the parents (`--cc`)
+ */
+"DenseCombined"
+/**
+ * Annotation for a line that's part of a moved block, pointing to where the block was moved to or from
+ */
+export type MovedLine = { 
+/**
+ * Index of the moved block, so adjacent blocks can be coloured differently (like git's zebra mode)
+ */
+block: number; 
+/**
+ * Path of the file containing the other side of the move
+ */
+path: string; start_line_number: number; 
+/**
+ * Exclusive
+ */
+end_line_number: number }
+export type PatchApplyMethod = 
+/**
+ * Apply the changes to the working tree with `git apply`
+ */
+"Apply" | 
+/**
+ * Create commits from an mbox with `git am`, keeping the original authors and messages
+ */
+"Am"
+/**
+ * Result of a dry run of applying a patch
+ */
+export type PatchCheck = { 
+/**
+ * Empty if the patch applies cleanly
+ */
+failures: PatchFailure[] }
+/**
+ * Why a patch or one of its hunks can't be applied
+ */
+export type PatchFailure = { path: string; 
+/**
+ * Line the hunk was expected at, None if the whole file failed
+ */
+line_number: number | null; reason: string; 
+/**
+ * Lines git couldn't find in the file when applying the hunk
+ */
+expected_lines: string | null }
+/**
+ * Commits to export as patch files
+ */
+export type PatchRevisions = 
+/**
+ * A single commit
+ */
+{ Commit: GitHash } | 
+/**
+ * Commits reachable from head but not base (`base..head`)
+ */
+{ Range: { base: string; head: string } }
+/**
+ * Emitted when branches or tags are created, deleted or moved
+ */
+export type RefsChangedEvent = { repository_id: number }
+export type Remote = { name: string; fetch_urls: string[]; 
+/**
+ * The same as `fetch_urls` unless push URLs are set separately
+ */
+push_urls: string[]; 
+/**
+ * e.g. `+refs/heads/*:refs/remotes/origin/*`
+ */
+fetch_refspecs: string[]; 
+/**
+ * Empty unless set, in which case they replace `push.default`
+ */
+push_refspecs: string[] }
+export type RemoteBranch = { 
+/**
+ * e.g. `["origin", "feat", "implement-stuff"]`
+ */
+name: string[]; hash: GitHash }
+export type Repository = { id: number; name: string; local_path: string; created_at: string; last_opened_at: string | null; last_fetched_at: string | null; has_changes: boolean; 
+/**
+ * Number of logged commands of each type to keep
+ */
+git_command_log_retention: number; 
+/**
+ * Commits the current branch is ahead of its upstream, None if it has no upstream
+ */
+ahead: number | null; 
+/**
+ * Commits the current branch is behind its upstream, None if it has no upstream
+ */
+behind: number | null; 
+/**
+ * When `has_changes`, `ahead` and `behind` were last updated
+ */
+last_checked_at: string | null; 
+/**
+ * Pinned repositories are listed first
+ */
+pinned: boolean; sort_order: number; 
+/**
+ * Set if this is a linked worktree of another added repository
+ */
+main_repository_id: number | null }
+/**
+ * Emitted after a repository is fetched in the background
+ */
+export type RepositoryFetchedEvent = { repository_id: number }
+/**
+ * Emitted when a repository is updated in the background, e.g. its changes or ahead/behind counts
+ */
+export type RepositoryStatusEvent = Repository
+/**
+ * Emitted when changes to the open repository can no longer be detected, e.g. because the
+ * system's limit on watched folders was reached
+ */
+export type RepositoryWatchFailedEvent = { repository_id: number; message: string }
+export type RevisionComparison = { 
+/**
+ * Commit the files are diffed from, which is the merge base in three-dot mode
+ */
+src_hash: GitHash; 
+/**
+ * Commit the files are diffed to
+ */
+dst_hash: GitHash; files: File[]; 
+/**
+ * Commits reachable from base but not head, newest first and at most a graph page of them
+ */
+base_commits: Commit[]; 
+/**
+ * Whether there are more base commits than fit in `base_commits`
+ */
+base_commits_truncated: boolean; 
+/**
+ * Commits reachable from head but not base, newest first and at most a graph page of them
+ */
+head_commits: Commit[]; 
+/**
+ * Whether there are more head commits than fit in `head_commits`
+ */
+head_commits_truncated: boolean }
+/**
+ * A command that's currently running, which can be cancelled
+ */
+export type RunningGitCommand = { id: number; command: string; command_type: GitCommandType }
+/**
+ * Application settings, stored in the database. Defaults match the `settings` table.
+ */
+export type Settings = { 
+/**
+ * Number of logged commands of each type to keep for newly added repositories
+ */
+git_command_log_retention: number; 
+/**
+ * Number of commits to load in the graph
+ */
+graph_page_size: number; 
+/**
+ * None to disable fetching in the background
+ */
+auto_fetch_interval_minutes: number | null; 
+/**
+ * Fetch every added repository in the background, not just the open one
+ */
+auto_fetch_all_repositories: boolean; diff_layout: DiffLayout; 
+/**
+ * Files larger than this many bytes will skip syntax highlighting and word diffs
+ */
+max_highlight_size: number; 
+/**
+ * Images larger than this many bytes will be shown as binary files instead
+ */
+max_image_size: number; 
+/**
+ * Path to the git executable, None to use `git` from the PATH
+ */
+git_executable: string | null; 
+/**
+ * Extra environment variables set for every git command
+ */
+git_environment: GitEnvironmentVariable[]; 
+/**
+ * Config passed with `-c key=value` to every git command
+ */
+git_config_overrides: GitConfigOverride[]; 
+/**
+ * Branch names that are never offered for cleanup, `*` matches any characters
+ */
+protected_branches: string[]; 
+/**
+ * Maximum time queries can run for before they're killed, None for no limit
+ */
+git_query_timeout_seconds: number | null; 
+/**
+ * Maximum time mutations can run for before they're killed, None for no limit
+ */
+git_mutation_timeout_seconds: number | null }
+/**
+ * Emitted when the settings are updated
+ */
+export type SettingsChangedEvent = Settings
+export type SplitDiffHunk = { 
+/**
+ * Raw header text, or None if the whole file was requested
+ */
+header: string | null; rows: SplitDiffRow[] }
+export type SplitDiffRow = { 
+/**
+ * None if this is a filler row alongside an added line
+ */
+src: LineDiff | null; 
+/**
+ * None if this is a filler row alongside a removed line
+ */
+dst: LineDiff | null }
+export type Submodule = { name: string; 
+/**
+ * Path relative to the top level of the repository
+ */
+path: string; 
+/**
+ * As configured in `.gitmodules`, which can be relative to the repository's remote
+ */
+url: string | null; 
+/**
+ * Commit recorded in the index, None if the submodule hasn't been added yet
+ */
+recorded_commit: GitHash | null; 
+/**
+ * Commit checked out in the submodule, None if it hasn't been initialized and updated
+ */
+checked_out_commit: GitHash | null; change: SubmoduleChange }
+/**
+ * How a submodule differs from the commit recorded for it
+ */
+export type SubmoduleChange = { 
+/**
+ * The submodule moved to another commit
+ */
+commit_changed: boolean; 
+/**
+ * The submodule has changes to tracked files
+ */
+modified_content: boolean; 
+/**
+ * The submodule has untracked files
+ */
+untracked_content: boolean }
+export type UpdateSubmodulesOptions = { 
+/**
+ * Initialize submodules that haven't been yet
+ */
+init: boolean; 
+/**
+ * Also update submodules inside the submodules
+ */
+recursive: boolean; 
+/**
+ * Check out the latest commit of each submodule's remote branch instead of the recorded one
+ */
+remote: boolean }
+/**
+ * If both are 0, it's in sync. If None, the tracked upstream is missing.
+ */
+export type UpstreamTrack = [number, number] | null
+/**
+ * Emitted when files in the work tree or the index change
+ */
+export type WorkingTreeChangedEvent = { repository_id: number }
+export type Worktree = { 
+/**
+ * Absolute path of the worktree
+ */
+path: string; 
+/**
+ * The main worktree is the one the repository was cloned or initialized in
+ */
+main: boolean; bare: boolean; 
+/**
+ * None for a bare repository
+ */
+head: GitHash | null; 
+/**
+ * e.g. `feat/implement-stuff`, None if HEAD is detached
+ */
+branch: string | null; 
+/**
+ * Some with the reason, which can be empty, if the worktree is locked
+ */
+locked: string | null; 
+/**
+ * Some with the reason if the worktree's folder is missing and `git worktree prune` would remove it
+ */
+prunable: string | null }
 
 /** tauri-specta globals **/
 
@@ -0,0 +1,68 @@
+import { Popover, PopoverTrigger } from '@radix-ui/react-popover'
+import { useQueryClient } from '@tanstack/react-query'
+import { PlusIcon } from 'lucide-react'
+import { useState } from 'react'
+import { commands } from '../../bindings'
+import { useCommandMutation } from '../../utils/useCommandMutation'
+import { IconButton } from '../UI/IconButton'
+import { PopoverContent } from '../UI/Popover'
+
+export const AddRemote = () => {
+  const queryClient = useQueryClient()
+  const [isOpen, setIsOpen] = useState(false)
+  const [name, setName] = useState('')
+  const [url, setUrl] = useState('')
+
+  const addRemote = useCommandMutation({
+    mutationFn: () => commands.addRemote(name.trim(), url.trim(), true),
+    onSuccess: async () => {
+      await Promise.all([
+        queryClient.invalidateQueries({ queryKey: ['branches'] }),
+        queryClient.invalidateQueries({ queryKey: ['graph'] }),
+      ])
+      setIsOpen(false)
+      setName('')
+      setUrl('')
+    },
+  })
+
+  return (
+    <Popover open={isOpen} onOpenChange={setIsOpen}>
+      <PopoverTrigger asChild>
+        <IconButton tooltip="Add remote" size="sm">
+          <PlusIcon />
+        </IconButton>
+      </PopoverTrigger>
+
+      <PopoverContent align="start" className="w-[320px]">
+        <form
+          className="flex flex-col gap-2 text-sm"
+          onSubmit={(e) => {
+            e.preventDefault()
+            addRemote.mutate()
+          }}
+        >
+          <input
+            className="bg-foreground/10 rounded-md h-8 px-2 outline-hidden border-none"
+            placeholder="Name, e.g. upstream"
+            value={name}
+            onChange={(e) => setName(e.target.value)}
+          />
+          <input
+            className="bg-foreground/10 rounded-md h-8 px-2 outline-hidden border-none font-mono"
+            placeholder="URL"
+            value={url}
+            onChange={(e) => setUrl(e.target.value)}
+          />
+          <button
+            type="submit"
+            className="self-end rounded-md h-8 px-3 bg-foreground/10 hover:enabled:bg-foreground/20 disabled:opacity-50"
+            disabled={!name.trim() || !url.trim() || addRemote.isPending}
+          >
+            {addRemote.isPending ? 'Fetching...' : 'Add and fetch'}
+          </button>
+        </form>
+      </PopoverContent>
+    </Popover>
+  )
+}
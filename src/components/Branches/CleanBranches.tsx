@@ -0,0 +1,134 @@
+import { Popover, PopoverTrigger } from '@radix-ui/react-popover'
+import { useQueryClient } from '@tanstack/react-query'
+import { PaintbrushIcon } from 'lucide-react'
+import { useEffect, useState } from 'react'
+import { type LocalBranch, commands } from '../../bindings'
+import { useOpenRepository } from '../../data/useOpenRepository'
+import { formatDate } from '../../utils/formatDate'
+import { useCommandMutation } from '../../utils/useCommandMutation'
+import { useCommandQuery } from '../../utils/useCommandQuery'
+import { IconButton } from '../UI/IconButton'
+import { PopoverContent } from '../UI/Popover'
+
+const DEFAULT_BASES = ['main', 'master']
+
+export const CleanBranches = ({ localBranches }: { localBranches: LocalBranch[] }) => {
+  const openRepository = useOpenRepository()
+  const queryClient = useQueryClient()
+  const [isOpen, setIsOpen] = useState(false)
+  const [base, setBase] = useState('')
+  const [selected, setSelected] = useState<string[]>([])
+
+  const branchNames = localBranches.map((branch) => branch.name.join('/'))
+  const defaultBase =
+    DEFAULT_BASES.find((name) => branchNames.includes(name)) ??
+    localBranches.find((branch) => branch.head)?.name.join('/') ??
+    ''
+
+  const { data: candidates } = useCommandQuery({
+    queryKey: ['branchCleanupCandidates', openRepository?.id, base],
+    enabled: isOpen && Boolean(base),
+    queryFn: () => commands.getBranchCleanupCandidates(base),
+  })
+
+  // Branches that only lost their upstream may have unmerged commits, so they have to be picked one by one
+  useEffect(() => {
+    setSelected(
+      candidates?.filter((candidate) => candidate.reasons.includes('Merged')).map((candidate) => candidate.name) ?? [],
+    )
+  }, [candidates])
+
+  const deleteBranches = useCommandMutation({
+    mutationFn: () => commands.deleteBranches(selected, base),
+    onSuccess: async () => {
+      await Promise.all([
+        queryClient.invalidateQueries({ queryKey: ['branches'] }),
+        queryClient.invalidateQueries({ queryKey: ['branchCleanupCandidates'] }),
+        queryClient.invalidateQueries({ queryKey: ['graph'] }),
+      ])
+      setIsOpen(false)
+    },
+  })
+
+  return (
+    <Popover
+      open={isOpen}
+      onOpenChange={(open) => {
+        if (open && !base) setBase(defaultBase)
+        setIsOpen(open)
+      }}
+    >
+      <PopoverTrigger asChild>
+        <IconButton tooltip="Clean merged branches" size="sm" disabled={!localBranches.length}>
+          <PaintbrushIcon />
+        </IconButton>
+      </PopoverTrigger>
+
+      <PopoverContent align="start" className="w-[380px] flex flex-col gap-2 text-sm">
+        <label className="flex items-center gap-2">
+          <span className="text-foreground/70">Merged into</span>
+          <select
+            className="bg-foreground/10 rounded-md h-8 px-2 outline-hidden border-none grow"
+            value={base}
+            onChange={(e) => setBase(e.target.value)}
+          >
+            {branchNames.map((name) => (
+              <option key={name} value={name}>
+                {name}
+              </option>
+            ))}
+          </select>
+        </label>
+
+        {candidates && !candidates.length && (
+          <p className="text-foreground/60">No merged or gone branches to clean up</p>
+        )}
+
+        <ul className="flex flex-col gap-1 max-h-[300px] overflow-auto">
+          {candidates?.map((candidate) => (
+            <li key={candidate.name}>
+              <label className="flex items-start gap-2">
+                <input
+                  type="checkbox"
+                  className="mt-1"
+                  checked={selected.includes(candidate.name)}
+                  onChange={(e) =>
+                    setSelected((selected) =>
+                      e.target.checked
+                        ? [...selected, candidate.name]
+                        : selected.filter((name) => name !== candidate.name),
+                    )
+                  }
+                />
+                <div className="flex flex-col min-w-0">
+                  <span className="font-mono truncate">
+                    {candidate.name}{' '}
+                    <span className="text-xs text-foreground/60">
+                      {candidate.reasons.map((reason) => (reason === 'Merged' ? 'merged' : 'upstream gone')).join(', ')}
+                    </span>
+                    {!candidate.reasons.includes('Merged') && (
+                      <span className="text-xs text-warn"> · not merged, will be force deleted</span>
+                    )}
+                  </span>
+                  <span className="text-xs text-foreground/60 truncate">
+                    {candidate.last_commit_message} · {candidate.last_commit_author},{' '}
+                    {formatDate(candidate.last_commit_date, 'd MMM yyyy')}
+                  </span>
+                </div>
+              </label>
+            </li>
+          ))}
+        </ul>
+
+        <button
+          type="button"
+          className="self-end rounded-md h-8 px-3 bg-foreground/10 hover:enabled:bg-foreground/20 disabled:opacity-50"
+          disabled={!selected.length || deleteBranches.isPending}
+          onClick={() => deleteBranches.mutate()}
+        >
+          Delete {selected.length} {selected.length === 1 ? 'branch' : 'branches'}
+        </button>
+      </PopoverContent>
+    </Popover>
+  )
+}
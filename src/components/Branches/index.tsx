@@ -2,10 +2,9 @@ import { Tooltip, TooltipTrigger } from '@radix-ui/react-tooltip'
 import {
   CircleDashedIcon,
   CircleIcon,
+  FolderTreeIcon,
   MoveDownIcon,
   MoveUpIcon,
-  PaintbrushIcon,
-  PlusIcon,
   TriangleAlertIcon,
 } from 'lucide-react'
 import { PanelGroup, PanelResizeHandle } from 'react-resizable-panels'
@@ -13,8 +12,9 @@ import { type UpstreamTrack, commands } from '../../bindings'
 import { useOpenRepository } from '../../data/useOpenRepository'
 import { useCommandQuery } from '../../utils/useCommandQuery'
 import { BranchListPanel } from '../BranchListPanel'
-import { IconButton } from '../UI/IconButton'
 import { TooltipContent } from '../UI/Tooltip'
+import { AddRemote } from './AddRemote'
+import { CleanBranches } from './CleanBranches'
 
 export const Branches = () => {
   const openRepository = useOpenRepository()
@@ -23,7 +23,6 @@ export const Branches = () => {
     queryKey: ['branches', openRepository?.id],
     enabled: Boolean(openRepository),
     queryFn: commands.getBranches,
-    refetchOnWindowFocus: true,
   })
 
   return (
@@ -31,11 +30,7 @@ export const Branches = () => {
       <BranchListPanel
         icon={<CircleIcon className="h-4 w-4" />}
         title="Local"
-        actions={
-          <IconButton tooltip="Clean merged branches" size="sm" disabled>
-            <PaintbrushIcon />
-          </IconButton>
-        }
+        actions={<CleanBranches localBranches={data?.local ?? []} />}
         items={
           data?.local.map((d) => ({
             hash: d.hash,
@@ -43,6 +38,16 @@ export const Branches = () => {
             children: (
               <>
                 <span className="mr-auto">{d.name[d.name.length - 1]}</span>
+                {!d.head && d.worktree_path && (
+                  <Tooltip>
+                    <TooltipTrigger asChild>
+                      <div>
+                        <FolderTreeIcon className="h-3 w-3" />
+                      </div>
+                    </TooltipTrigger>
+                    <TooltipContent>Checked out in {d.worktree_path}</TooltipContent>
+                  </Tooltip>
+                )}
                 <BranchDelta upstreamTrack={d.upstream_track} />
               </>
             ),
@@ -55,11 +60,7 @@ export const Branches = () => {
       <BranchListPanel
         icon={<CircleDashedIcon className="h-4 w-4" />}
         title="Remote"
-        actions={
-          <IconButton tooltip="Add remote" size="sm" disabled>
-            <PlusIcon />
-          </IconButton>
-        }
+        actions={<AddRemote />}
         items={
           data?.remote.map((d) => ({
             hash: d.hash,
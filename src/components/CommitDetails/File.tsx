@@ -11,7 +11,7 @@ export const CommitFile = ({
   return (
     <div
       className={cn(
-        'px-3 py-1 text-sm active:bg-foreground/20',
+        'px-3 py-1 text-sm active:bg-foreground/20 flex items-center gap-2',
         isSelected ? 'bg-foreground/20' : 'hover:bg-foreground/10',
       )}
       onPointerDown={() => onSelect()}
@@ -24,6 +24,30 @@ export const CommitFile = ({
         tooltipProps={{ side: 'left', sideOffset: 24 }}
         iconClassName="h-3 w-3"
       />
+      {file.submodule ? (
+        <span className="ml-auto shrink-0 text-xs font-mono text-foreground/60">
+          {file.submodule.commit_changed && file.src_hash && file.dst_hash
+            ? `${file.src_hash.slice(0, 7)}→${file.dst_hash.slice(0, 7)}`
+            : file.submodule.modified_content
+              ? 'modified content'
+              : file.submodule.untracked_content
+                ? 'untracked files'
+                : 'submodule'}
+        </span>
+      ) : (
+        file.stats && (
+          <span className="ml-auto shrink-0 text-xs font-mono">
+            {file.stats.binary ? (
+              <span className="text-foreground/60">bin</span>
+            ) : (
+              <>
+                <span className="text-success">+{file.stats.insertions}</span>{' '}
+                <span className="text-error">-{file.stats.deletions}</span>
+              </>
+            )}
+          </span>
+        )
+      )}
     </div>
   )
 }
@@ -1,9 +1,13 @@
 import { Tooltip, TooltipTrigger } from '@radix-ui/react-tooltip'
 import { FileInputIcon, MinusIcon, PencilIcon, PlusIcon } from 'lucide-react'
-import type { File } from '../../bindings'
+import type { DiffStats, File } from '../../bindings'
 import { TooltipContent } from '../UI/Tooltip'
 
-export const CommitStats = ({ files, className }: { files: File[]; className?: string }) => {
+export const CommitStats = ({
+  files,
+  stats: diffStats,
+  className,
+}: { files: File[]; stats: DiffStats | null; className?: string }) => {
   const stats = calculateStats(files)
 
   return (
@@ -53,6 +57,12 @@ export const CommitStats = ({ files, className }: { files: File[]; className?: s
             <TooltipContent>{stats.renamed} renamed</TooltipContent>
           </Tooltip>
         )}
+        {diffStats && (
+          <div className="ml-auto flex items-center gap-1.5 text-xs font-semibold">
+            <span className="text-success">+{diffStats.insertions}</span>
+            <span className="text-error">-{diffStats.deletions}</span>
+          </div>
+        )}
       </div>
     </div>
   )
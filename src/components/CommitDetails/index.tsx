@@ -1,12 +1,20 @@
+import { Select, SelectValue } from '@radix-ui/react-select'
 import { useAtom, useAtomValue } from 'jotai'
 import { LoaderCircleIcon } from 'lucide-react'
 import { Panel, PanelGroup, PanelResizeHandle } from 'react-resizable-panels'
-import { type CommitUser, commands } from '../../bindings'
+import { type CommitUser, type MergeDiffMode, commands } from '../../bindings'
 import { useOpenRepository } from '../../data/useOpenRepository'
-import { calculateFileId, selectedCommitHashAtom, selectedFileIdAtom } from '../../ui-state'
+import {
+  calculateFileId,
+  mergeDiffModeAtom,
+  mergeDiffModeForCommit,
+  selectedCommitHashAtom,
+  selectedFileIdAtom,
+} from '../../ui-state'
 import { formatDate } from '../../utils/formatDate'
 import { useCommandQuery } from '../../utils/useCommandQuery'
 import { Avatar } from '../UI/Avatar'
+import { SelectContent, SelectItem, SelectTrigger } from '../UI/Select'
 import { CommitFile } from './File'
 import { CommitStats } from './Stats'
 
@@ -14,6 +22,7 @@ export const CommitDetails = () => {
   const openRepository = useOpenRepository()
   const selectedCommitHash = useAtomValue(selectedCommitHashAtom)
   const [selectedFileId, setSelectedFileId] = useAtom(selectedFileIdAtom)
+  const [mergeDiffMode, setMergeDiffMode] = useAtom(mergeDiffModeAtom)
 
   const { data: commits } = useCommandQuery({
     queryKey: ['graph', openRepository?.id],
@@ -21,13 +30,14 @@ export const CommitDetails = () => {
     enabled: Boolean(openRepository),
   })
 
-  const { data: files } = useCommandQuery({
-    queryKey: ['graph', openRepository?.id, selectedCommitHash],
-    queryFn: () => commands.getCommitFiles(selectedCommitHash as string),
-    enabled: Boolean(openRepository && selectedCommitHash),
-  })
-
   const selectedCommit = commits?.find((c) => c.hash === selectedCommitHash)
+  const commitMergeDiffMode = mergeDiffModeForCommit(mergeDiffMode, selectedCommit?.parent_hashes.length ?? 0)
+
+  const { data: commitFiles } = useCommandQuery({
+    queryKey: ['graph', openRepository?.id, selectedCommitHash, commitMergeDiffMode],
+    queryFn: () => commands.getCommitFiles(selectedCommitHash as string, commitMergeDiffMode),
+    enabled: Boolean(openRepository && selectedCommit),
+  })
 
   return (
     <PanelGroup direction="vertical">
@@ -66,13 +76,13 @@ export const CommitDetails = () => {
               </>
             )}
 
-            {files && (
+            {commitFiles && (
               <>
-                <CommitStats className="mt-4 mb-1.5" files={files} />
+                <CommitStats className="mt-4 mb-1.5" files={commitFiles.files} stats={commitFiles.stats} />
 
                 <div className="bg-surface rounded-md flex-1 flex flex-col min-h-0">
                   <div className="overflow-y-auto flex-1 pt-1">
-                    {files.map((file) => {
+                    {commitFiles.files.map((file) => {
                       const id = calculateFileId(selectedCommit.hash, file.src_path)
                       return (
                         <CommitFile
@@ -94,6 +104,25 @@ export const CommitDetails = () => {
                       <span className="text-foreground/60">Parents</span>{' '}
                       {selectedCommit.parent_hashes.map((p) => p.slice(0, 6)).join(', ') || 'none'}
                     </div>
+                    {selectedCommit.parent_hashes.length > 1 && (
+                      <Select
+                        value={serializeMergeDiffMode(commitMergeDiffMode)}
+                        onValueChange={(v) => setMergeDiffMode(deserializeMergeDiffMode(v))}
+                      >
+                        <SelectTrigger className="ml-auto text-xs">
+                          <SelectValue />
+                        </SelectTrigger>
+                        <SelectContent align="end">
+                          {selectedCommit.parent_hashes.map((hash, i) => (
+                            <SelectItem key={hash} value={`Parent-${i + 1}`}>
+                              Parent {i + 1} ({hash.slice(0, 6)})
+                            </SelectItem>
+                          ))}
+                          <SelectItem value="Combined">Combined</SelectItem>
+                          <SelectItem value="DenseCombined">Conflict resolutions</SelectItem>
+                        </SelectContent>
+                      </Select>
+                    )}
                   </div>
                 </div>
               </>
@@ -105,6 +134,13 @@ export const CommitDetails = () => {
   )
 }
 
+const serializeMergeDiffMode = (mode: MergeDiffMode) => (typeof mode === 'object' ? `Parent-${mode.Parent}` : mode)
+
+const deserializeMergeDiffMode = (value: string): MergeDiffMode => {
+  if (value === 'Combined' || value === 'DenseCombined') return value
+  return { Parent: Number(value.replace('Parent-', '')) }
+}
+
 const User = ({ user }: { user: CommitUser }) => {
   return (
     <div className="mt-1 flex gap-2 items-center">
@@ -1,12 +1,33 @@
 import { Fragment } from 'react'
-import type { DiffStatus, FileDiff, LineDiff } from '../../bindings'
+import type {
+  BinaryFile,
+  CombinedDiffHunk,
+  CombinedLineDiff,
+  DiffHunk,
+  DiffStatus,
+  FileDiff,
+  GitHash,
+  ImageFile,
+  LineDiff,
+  MovedLine,
+  SplitDiffHunk,
+} from '../../bindings'
 import { cn } from '../../utils/cn'
 
 export const Diff = ({ diff }: { diff: FileDiff }) => {
+  if ('Image' in diff) return <ImageDiff src={diff.Image.src} dst={diff.Image.dst} />
+  if ('Binary' in diff) return <BinaryDiff src={diff.Binary.src} dst={diff.Binary.dst} />
+  if ('Submodule' in diff) return <SubmoduleDiff src={diff.Submodule.src} dst={diff.Submodule.dst} />
+  if ('Split' in diff) return <SplitDiff hunks={diff.Split} />
+  if ('Combined' in diff) return <CombinedDiff hunks={diff.Combined} />
+  return <TextDiff hunks={diff.Text} />
+}
+
+const TextDiff = ({ hunks }: { hunks: DiffHunk[] }) => {
   return (
     <pre className="flex-1 overflow-auto py-2 text-sm select-text cursor-auto">
       <code className="w-max min-w-full grid grid-cols-[auto_auto_1fr] font-mono">
-        {diff.map((hunk) => (
+        {hunks.map((hunk) => (
           <Fragment key={hunk.header}>
             <div className="bg-info/30 py-1 px-3 text-foreground/70 col-span-full">{hunk.header}</div>
             {hunk.lines.map((line, i) => (
@@ -19,9 +40,195 @@ export const Diff = ({ diff }: { diff: FileDiff }) => {
   )
 }
 
+const SplitDiff = ({ hunks }: { hunks: SplitDiffHunk[] }) => {
+  return (
+    <pre className="flex-1 overflow-auto py-2 text-sm select-text cursor-auto">
+      <code className="w-max min-w-full grid grid-cols-[auto_1fr_auto_1fr] font-mono">
+        {hunks.map((hunk) => (
+          <Fragment key={hunk.header}>
+            <div className="bg-info/30 py-1 px-3 text-foreground/70 col-span-full">{hunk.header}</div>
+            {hunk.rows.map((row, i) => (
+              <Fragment key={`${hunk.header}-${i}`}>
+                <SplitDiffLine id={`${hunk.header}-${i}-src`} line={row.src} lineNumber={row.src?.src_line_number} />
+                <SplitDiffLine id={`${hunk.header}-${i}-dst`} line={row.dst} lineNumber={row.dst?.dst_line_number} />
+              </Fragment>
+            ))}
+          </Fragment>
+        ))}
+      </code>
+    </pre>
+  )
+}
+
+const CombinedDiff = ({ hunks }: { hunks: CombinedDiffHunk[] }) => {
+  if (hunks.length === 0) {
+    return (
+      <div className="flex-1 flex items-center justify-center text-sm text-foreground/60">
+        No changes compared to the parents
+      </div>
+    )
+  }
+
+  const parentCount = hunks[0].lines[0]?.statuses.length ?? 2
+
+  return (
+    <pre className="flex-1 overflow-auto py-2 text-sm select-text cursor-auto">
+      <code
+        className="w-max min-w-full grid font-mono"
+        style={{ gridTemplateColumns: `repeat(${parentCount + 1}, auto) 1fr` }}
+      >
+        {hunks.map((hunk) => (
+          <Fragment key={hunk.header}>
+            <div className="bg-info/30 py-1 px-3 text-foreground/70 col-span-full">{hunk.header}</div>
+            {hunk.lines.map((line, i) => (
+              <CombinedDiffLine key={`${hunk.header}-${i}`} id={`${hunk.header}-${i}`} line={line} />
+            ))}
+          </Fragment>
+        ))}
+      </code>
+    </pre>
+  )
+}
+
+const CombinedDiffLine = ({ line, id }: { line: CombinedLineDiff; id: string }) => {
+  const isRemoved = line.dst_line_number === null
+  const isAdded = !isRemoved && line.statuses.includes('Added')
+  const lineNumberClassName = cn(
+    'px-2 select-none cursor-default text-right',
+    !isAdded && !isRemoved && 'text-foreground/60',
+    isAdded && 'bg-success/40',
+    isRemoved && 'bg-error/40',
+  )
+
+  return (
+    <>
+      {line.src_line_numbers.map((lineNumber, i) => (
+        <span key={`${id}-${i}`} className={lineNumberClassName}>
+          {lineNumber ?? ''}
+        </span>
+      ))}
+      <span className={lineNumberClassName}>{line.dst_line_number ?? ''}</span>
+      <div className={cn('pr-3', isAdded && 'bg-success/20', isRemoved && 'bg-error/20')}>
+        <span className="inline-block select-none cursor-default px-2 whitespace-pre">
+          {line.statuses.map((status) => (status === 'Added' ? '+' : status === 'Removed' ? '-' : ' ')).join('')}
+        </span>
+        {line.fragments.map((word, i) => (
+          <DiffWord key={`${id}-${i}`} className={cn(...(word.class ?? []))}>
+            {word.text}
+          </DiffWord>
+        ))}
+      </div>
+    </>
+  )
+}
+
+const SplitDiffLine = ({ line, lineNumber, id }: { line: LineDiff | null; lineNumber?: number | null; id: string }) => {
+  if (!line) return <div className="col-span-2 bg-foreground/5" />
+
+  return (
+    <>
+      <span
+        className={cn(
+          'px-2 select-none cursor-default text-right',
+          line.status === 'Unmodified' && 'text-foreground/60',
+          line.status === 'Added' && 'bg-success/40',
+          line.status === 'Removed' && 'bg-error/40',
+          line.moved && movedClassName(line.moved, 'dark'),
+        )}
+      >
+        {lineNumber ?? ''}
+      </span>
+      <div
+        className={cn(
+          'px-2',
+          line.status === 'Added' && 'bg-success/20',
+          line.status === 'Removed' && 'bg-error/20',
+          line.moved && movedClassName(line.moved, 'light'),
+        )}
+        title={line.moved ? movedTitle(line.status, line.moved) : undefined}
+      >
+        {line.fragments.map((word, i) => (
+          <DiffWord
+            key={`${id}-${i}`}
+            className={cn(
+              word.status === 'Removed' && 'bg-error/20',
+              word.status === 'Added' && 'bg-success/20',
+              ...(word.class ?? []),
+            )}
+          >
+            {word.text}
+          </DiffWord>
+        ))}
+      </div>
+    </>
+  )
+}
+
+const formatSize = (size: number) => {
+  if (size < 1024) return `${size} B`
+  if (size < 1024 * 1024) return `${(size / 1024).toFixed(1)} KB`
+  return `${(size / 1024 / 1024).toFixed(1)} MB`
+}
+
+const ImageDiff = ({ src, dst }: { src: ImageFile | null; dst: ImageFile | null }) => {
+  return (
+    <div className="flex-1 overflow-auto p-3 grid grid-cols-2 gap-3">
+      {[src, dst].map((image, i) => (
+        <div
+          key={i}
+          className={cn(
+            'flex flex-col items-center gap-2 rounded-md p-2',
+            i === 0 ? 'bg-error/10' : 'bg-success/10',
+          )}
+        >
+          {image ? (
+            <>
+              <img
+                src={`data:${image.mime_type};base64,${image.base64}`}
+                alt={i === 0 ? 'Before' : 'After'}
+                className="max-w-full"
+              />
+              <span className="text-sm text-foreground/60">{formatSize(image.size)}</span>
+            </>
+          ) : (
+            <span className="text-sm text-foreground/60">{i === 0 ? 'Added' : 'Deleted'}</span>
+          )}
+        </div>
+      ))}
+    </div>
+  )
+}
+
+const BinaryDiff = ({ src, dst }: { src: BinaryFile | null; dst: BinaryFile | null }) => {
+  return (
+    <div className="flex-1 flex flex-col items-center justify-center gap-1 text-sm text-foreground/60">
+      <span>Binary file{dst ? ` (${dst.mime_type})` : ''}</span>
+      <span>
+        {src ? formatSize(src.size) : 'Added'} → {dst ? formatSize(dst.size) : 'Deleted'}
+      </span>
+    </div>
+  )
+}
+
+const SubmoduleDiff = ({ src, dst }: { src: GitHash | null; dst: GitHash | null }) => {
+  return (
+    <div className="flex-1 flex flex-col items-center justify-center gap-1 text-sm text-foreground/60">
+      <span>Submodule</span>
+      <span className="font-mono">
+        {src ? src.slice(0, 7) : 'Added'} → {dst ? dst.slice(0, 7) : 'Removed'}
+      </span>
+    </div>
+  )
+}
+
 const DiffLine = ({ line, id }: { line: LineDiff; id: string }) => {
   return (
-    <Line srcLineNumber={line.src_line_number} dstLineNumber={line.dst_line_number} status={line.status}>
+    <Line
+      srcLineNumber={line.src_line_number}
+      dstLineNumber={line.dst_line_number}
+      status={line.status}
+      moved={line.moved}
+    >
       {line.fragments.map((word, i) => (
         <DiffWord
           key={`${id}-${i}`}
@@ -38,24 +245,53 @@ const DiffLine = ({ line, id }: { line: LineDiff; id: string }) => {
   )
 }
 
+/** Moved blocks alternate colours like git's zebra mode */
+const movedClassName = (moved: MovedLine, opacity: 'light' | 'dark') =>
+  moved.block % 2 === 0
+    ? opacity === 'light'
+      ? 'bg-info/20'
+      : 'bg-info/40'
+    : opacity === 'light'
+      ? 'bg-warn/20'
+      : 'bg-warn/40'
+
+const movedTitle = (status: DiffStatus, moved: MovedLine) =>
+  `Moved ${status === 'Removed' ? 'to' : 'from'} ${moved.path}:${moved.start_line_number}-${moved.end_line_number - 1}`
+
 const Line = ({
   status,
   children,
   srcLineNumber,
   dstLineNumber,
-}: { status?: DiffStatus; children: React.ReactNode; srcLineNumber: number | null; dstLineNumber: number | null }) => {
+  moved,
+}: {
+  status?: DiffStatus
+  children: React.ReactNode
+  srcLineNumber: number | null
+  dstLineNumber: number | null
+  moved?: MovedLine | null
+}) => {
   const lineNumberClassName = cn(
     'px-2 select-none cursor-default text-right',
     status === 'Unmodified' && 'text-foreground/60',
     status === 'Added' && 'bg-success/40',
     status === 'Removed' && 'bg-error/40',
+    moved && movedClassName(moved, 'dark'),
   )
 
   return (
     <>
       <span className={lineNumberClassName}>{srcLineNumber ?? ''}</span>
       <span className={lineNumberClassName}>{dstLineNumber ?? ''}</span>
-      <div className={cn('pr-3', status === 'Added' && 'bg-success/20', status === 'Removed' && 'bg-error/20')}>
+      <div
+        className={cn(
+          'pr-3',
+          status === 'Added' && 'bg-success/20',
+          status === 'Removed' && 'bg-error/20',
+          moved && movedClassName(moved, 'light'),
+        )}
+        title={status && moved ? movedTitle(status, moved) : undefined}
+      >
         <span className="inline-block select-none cursor-default px-2">
           {status === 'Unmodified' && ' '}
           {status === 'Added' && '+'}
@@ -1,9 +1,17 @@
 import { useAtom, useAtomValue } from 'jotai'
-import { LoaderCircleIcon, XIcon } from 'lucide-react'
+import { Columns2Icon, LoaderCircleIcon, Rows2Icon, XIcon } from 'lucide-react'
 import { useEffect, useState } from 'react'
 import { commands } from '../../bindings'
 import { useOpenRepository } from '../../data/useOpenRepository'
-import { calculateFileId, selectedCommitHashAtom, selectedFileIdAtom } from '../../ui-state'
+import { useSettings } from '../../data/useSettings'
+import {
+  calculateFileId,
+  diffLayoutAtom,
+  mergeDiffModeAtom,
+  mergeDiffModeForCommit,
+  selectedCommitHashAtom,
+  selectedFileIdAtom,
+} from '../../ui-state'
 import { cn } from '../../utils/cn'
 import { useCommandQuery } from '../../utils/useCommandQuery'
 import { FileName } from '../FileName'
@@ -14,11 +22,28 @@ export const FileDiff = () => {
   const openRepository = useOpenRepository()
   const selectedCommitHash = useAtomValue(selectedCommitHashAtom)
   const [selectedFileId, setSelectedFileId] = useAtom(selectedFileIdAtom)
+  const [diffLayout, setDiffLayout] = useAtom(diffLayoutAtom)
+  const mergeDiffMode = useAtomValue(mergeDiffModeAtom)
 
-  const { data: files } = useCommandQuery({
-    queryKey: ['graph', openRepository?.id, selectedCommitHash],
-    queryFn: () => commands.getCommitFiles(selectedCommitHash as string),
-    enabled: Boolean(openRepository && selectedCommitHash),
+  // Use the default layout from the settings until it's toggled
+  const settings = useSettings()
+  useEffect(() => {
+    if (settings) setDiffLayout(settings.diff_layout)
+  }, [settings?.diff_layout])
+
+  const { data: commits } = useCommandQuery({
+    queryKey: ['graph', openRepository?.id],
+    queryFn: commands.getGraph,
+    enabled: Boolean(openRepository),
+  })
+
+  const selectedCommit = commits?.find((c) => c.hash === selectedCommitHash)
+  const commitMergeDiffMode = mergeDiffModeForCommit(mergeDiffMode, selectedCommit?.parent_hashes.length ?? 0)
+
+  const { data: commitFiles } = useCommandQuery({
+    queryKey: ['graph', openRepository?.id, selectedCommitHash, commitMergeDiffMode],
+    queryFn: () => commands.getCommitFiles(selectedCommitHash as string, commitMergeDiffMode),
+    enabled: Boolean(openRepository && selectedCommit),
   })
 
   // Keep file loaded on the page after deselected while animating away
@@ -28,12 +53,17 @@ export const FileDiff = () => {
   }, [selectedFileId])
 
   const selectedFile = selectedCommitHash
-    ? files?.find((f) => calculateFileId(selectedCommitHash, f.src_path) === cachedFileId)
+    ? commitFiles?.files.find((f) => calculateFileId(selectedCommitHash, f.src_path) === cachedFileId)
     : undefined
 
   const { data: diff } = useCommandQuery({
-    queryKey: ['diff', selectedCommitHash, selectedFile?.src_path],
-    queryFn: () => commands.getFileDiff(selectedCommitHash as string, selectedFile?.src_path as string),
+    queryKey: ['diff', selectedCommitHash, selectedFile?.src_path, diffLayout, commitMergeDiffMode],
+    queryFn: () =>
+      commands.getFileDiff(
+        { Commit: { hash: selectedCommitHash as string, merge_diff_mode: commitMergeDiffMode } },
+        selectedFile?.src_path as string,
+        diffLayout,
+      ),
     enabled: Boolean(openRepository && selectedCommitHash && selectedFile),
   })
 
@@ -52,6 +82,13 @@ export const FileDiff = () => {
             tooltipProps={{ align: 'start' }}
           />
 
+          <IconButton
+            tooltip={diffLayout === 'Unified' ? 'Split view' : 'Unified view'}
+            onClick={() => setDiffLayout(diffLayout === 'Unified' ? 'Split' : 'Unified')}
+          >
+            {diffLayout === 'Unified' ? <Columns2Icon /> : <Rows2Icon />}
+          </IconButton>
+
           <IconButton tooltip="Close" onClick={() => setSelectedFileId(null)}>
             <XIcon />
           </IconButton>
@@ -2,6 +2,7 @@ import { useAtom, useSetAtom } from 'jotai'
 import { useEffect } from 'react'
 import { commands } from '../../bindings'
 import { useOpenRepository } from '../../data/useOpenRepository'
+import { useRepositoryWatcher } from '../../data/useRepositoryWatcher'
 import { selectedCommitHashAtom, selectedFileIdAtom } from '../../ui-state'
 import { useCommandQuery } from '../../utils/useCommandQuery'
 import { CommitStats } from '../CommitDetails/Stats'
@@ -9,19 +10,18 @@ import { CommitRow } from './CommitRow'
 
 export const Graph = () => {
   const openRepository = useOpenRepository()
+  useRepositoryWatcher()
 
   const { data: commits } = useCommandQuery({
     queryKey: ['graph', openRepository?.id],
     queryFn: commands.getGraph,
     enabled: Boolean(openRepository),
-    refetchOnWindowFocus: true,
   })
 
   const { data: changes } = useCommandQuery({
     queryKey: ['status'],
     queryFn: commands.getChangedFiles,
     enabled: Boolean(openRepository),
-    refetchOnWindowFocus: true,
   })
 
   const [selectedHash, _setSelectedHash] = useAtom(selectedCommitHashAtom)
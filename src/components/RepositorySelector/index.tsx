@@ -1,10 +1,21 @@
 import { DropdownMenu, DropdownMenuTrigger } from '@radix-ui/react-dropdown-menu'
 import { Tooltip, TooltipTrigger } from '@radix-ui/react-tooltip'
 import { useQueryClient } from '@tanstack/react-query'
-import { open } from '@tauri-apps/plugin-dialog'
+import { ask, open } from '@tauri-apps/plugin-dialog'
 import { useSetAtom } from 'jotai'
-import { CheckIcon, ChevronDownIcon, CopyIcon, FolderIcon, PlusIcon } from 'lucide-react'
-import { commands } from '../../bindings'
+import {
+  ArrowDownIcon,
+  CheckIcon,
+  ChevronDownIcon,
+  CopyIcon,
+  FolderIcon,
+  FolderTreeIcon,
+  PinIcon,
+  PlusIcon,
+  TrashIcon,
+} from 'lucide-react'
+import { useEffect } from 'react'
+import { type Repository, commands, events } from '../../bindings'
 import { useOpenRepository } from '../../data/useOpenRepository'
 import { selectedCommitHashAtom } from '../../ui-state'
 import { cn } from '../../utils/cn'
@@ -23,6 +34,19 @@ export const RepositorySelector = () => {
     queryFn: commands.getRepositories,
   })
 
+  // Keep changes and ahead/behind counts up to date as they're checked in the background
+  useEffect(() => {
+    const unlisten = events.repositoryStatusEvent.listen(({ payload }) => {
+      queryClient.setQueryData<Repository[]>(['repositories'], (repos) =>
+        repos?.map((repo) => (repo.id === payload.id ? payload : repo)),
+      )
+      if (payload.id === openRepository?.id) queryClient.setQueryData(['openRepository'], payload)
+    })
+    return () => {
+      unlisten.then((f) => f())
+    }
+  }, [openRepository?.id])
+
   const addRepository = useCommandMutation({
     mutationFn: commands.addRepositoryFromPath,
     onSuccess: () =>
@@ -32,6 +56,20 @@ export const RepositorySelector = () => {
       ]),
   })
 
+  const setPinned = useCommandMutation({
+    mutationFn: ({ id, pinned }: { id: number; pinned: boolean }) => commands.setRepositoryPinned(id, pinned),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: ['repositories'] }),
+  })
+
+  const removeRepository = useCommandMutation({
+    mutationFn: commands.removeRepository,
+    onSuccess: () =>
+      Promise.all([
+        queryClient.invalidateQueries({ queryKey: ['openRepository'] }),
+        queryClient.invalidateQueries({ queryKey: ['repositories'] }),
+      ]),
+  })
+
   const setSelectedCommitHash = useSetAtom(selectedCommitHashAtom)
 
   const setOpenRepository = useCommandMutation({
@@ -51,13 +89,58 @@ export const RepositorySelector = () => {
       <DropdownMenuContent align="end">
         {repositories?.map((repo) => (
           <DropdownMenuItem
-            key={repo.name}
-            className={cn('justify-end pl-3 text-base gap-2', openRepository?.id === repo.id && 'font-semibold')}
+            key={repo.id}
+            className={cn('group justify-end pl-3 text-base gap-2', openRepository?.id === repo.id && 'font-semibold')}
             onClick={() => {
               setOpenRepository.mutate(repo.id)
             }}
           >
+            <button
+              type="button"
+              title="Remove from list"
+              className="mr-auto hidden group-hover:block opacity-60 hover:opacity-100"
+              onClick={async (e) => {
+                e.stopPropagation()
+                const confirmed = await ask(
+                  `Remove ${repo.name} from the list? Its command log will be deleted, the folder is kept.`,
+                  { title: 'Remove repository', kind: 'warning', okLabel: 'Remove' },
+                )
+                if (confirmed) removeRepository.mutate(repo.id)
+              }}
+            >
+              <TrashIcon className="h-3 w-3" />
+            </button>
+            <button
+              type="button"
+              title={repo.pinned ? 'Unpin' : 'Pin'}
+              className={cn('opacity-60 hover:opacity-100', !repo.pinned && 'hidden group-hover:block')}
+              onClick={(e) => {
+                e.stopPropagation()
+                setPinned.mutate({ id: repo.id, pinned: !repo.pinned })
+              }}
+            >
+              <PinIcon className={cn('h-3 w-3', repo.pinned && 'fill-current')} />
+            </button>
             {repo.name}{' '}
+            {repo.main_repository_id !== null && (
+              <Tooltip>
+                <TooltipTrigger className="flex items-center text-foreground/60">
+                  <FolderTreeIcon className="h-3 w-3" />
+                </TooltipTrigger>
+                <TooltipContent>
+                  Worktree of {repositories.find((main) => main.id === repo.main_repository_id)?.name}
+                </TooltipContent>
+              </Tooltip>
+            )}
+            {(repo.behind ?? 0) > 0 && (
+              <Tooltip>
+                <TooltipTrigger className="flex items-center text-xs text-foreground/60">
+                  <ArrowDownIcon className="h-3 w-3" />
+                  {repo.behind}
+                </TooltipTrigger>
+                <TooltipContent>{repo.behind} commits behind upstream</TooltipContent>
+              </Tooltip>
+            )}
             {openRepository?.id === repo.id ? (
               <CheckIcon className="h-3 w-3" />
             ) : repo.has_changes ? (
@@ -65,7 +148,7 @@ export const RepositorySelector = () => {
                 <TooltipTrigger className="h-3 w-3 flex items-center justify-center">
                   <div className="rounded-full h-1 w-1 bg-foreground/40" />
                 </TooltipTrigger>
-                <TooltipContent>New updates</TooltipContent>
+                <TooltipContent>Uncommitted changes</TooltipContent>
               </Tooltip>
             ) : (
               <div className="h-3 w-3" />
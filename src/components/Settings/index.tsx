@@ -0,0 +1,132 @@
+import { Popover, PopoverTrigger } from '@radix-ui/react-popover'
+import { SettingsIcon } from 'lucide-react'
+import { useEffect } from 'react'
+import { type Settings as SettingsType, commands } from '../../bindings'
+import { useSettings } from '../../data/useSettings'
+import { cn } from '../../utils/cn'
+import { useCommandMutation } from '../../utils/useCommandMutation'
+import { useCommandQuery } from '../../utils/useCommandQuery'
+import { toast } from '../Toaster'
+import { IconButton } from '../UI/IconButton'
+import { PopoverContent } from '../UI/Popover'
+
+export const Settings = () => {
+  const settings = useSettings()
+
+  // Git is checked again whenever the settings change
+  const { data: gitVersion } = useCommandQuery({
+    queryKey: ['gitVersion', settings],
+    queryFn: commands.getGitVersion,
+  })
+
+  useEffect(() => {
+    if (gitVersion?.error) toast({ variant: 'error', title: 'Git is unavailable', children: gitVersion.error })
+  }, [gitVersion?.error])
+
+  const updateSettings = useCommandMutation({
+    mutationFn: commands.updateSettings,
+  })
+
+  const update = (changes: Partial<SettingsType>) => {
+    if (settings) updateSettings.mutate({ ...settings, ...changes })
+  }
+
+  return (
+    <Popover>
+      <PopoverTrigger asChild>
+        <IconButton tooltip="Settings" disabled={!settings}>
+          <SettingsIcon />
+        </IconButton>
+      </PopoverTrigger>
+
+      {settings && (
+        <PopoverContent align="end" className="w-[320px] flex flex-col gap-3 text-sm">
+          <NumberSetting
+            label="Commits in graph"
+            value={settings.graph_page_size}
+            onChange={(graph_page_size) => update({ graph_page_size })}
+          />
+          <NumberSetting
+            label="Auto fetch interval (minutes, 0 to disable)"
+            value={settings.auto_fetch_interval_minutes ?? 0}
+            onChange={(minutes) => update({ auto_fetch_interval_minutes: minutes > 0 ? minutes : null })}
+          />
+          <label className="flex items-center gap-2">
+            <input
+              type="checkbox"
+              checked={settings.auto_fetch_all_repositories}
+              onChange={(e) => update({ auto_fetch_all_repositories: e.target.checked })}
+            />
+            <span className="text-foreground/70">Auto fetch all repositories, not just the open one</span>
+          </label>
+          <NumberSetting
+            label="Command log entries kept for new repositories"
+            value={settings.git_command_log_retention}
+            onChange={(git_command_log_retention) => update({ git_command_log_retention })}
+          />
+          <NumberSetting
+            label="Query timeout (seconds, 0 for no limit)"
+            value={settings.git_query_timeout_seconds ?? 0}
+            onChange={(seconds) => update({ git_query_timeout_seconds: seconds > 0 ? seconds : null })}
+          />
+          <NumberSetting
+            label="Mutation timeout (seconds, 0 for no limit)"
+            value={settings.git_mutation_timeout_seconds ?? 0}
+            onChange={(seconds) => update({ git_mutation_timeout_seconds: seconds > 0 ? seconds : null })}
+          />
+          <label className="flex flex-col gap-1">
+            <span className="text-foreground/70">Protected branches (comma separated, * as wildcard)</span>
+            <input
+              className="bg-foreground/10 rounded-md h-8 px-2 outline-hidden border-none font-mono"
+              defaultValue={settings.protected_branches.join(', ')}
+              onBlur={(e) =>
+                update({
+                  protected_branches: e.target.value
+                    .split(',')
+                    .map((name) => name.trim())
+                    .filter(Boolean),
+                })
+              }
+            />
+          </label>
+          <label className="flex flex-col gap-1">
+            <span className="text-foreground/70">Git executable</span>
+            <input
+              className="bg-foreground/10 rounded-md h-8 px-2 outline-hidden border-none font-mono"
+              placeholder="git"
+              defaultValue={settings.git_executable ?? ''}
+              onBlur={(e) => update({ git_executable: e.target.value.trim() || null })}
+            />
+            {gitVersion && (
+              <span className={cn('text-xs', gitVersion.error ? 'text-error' : 'text-foreground/60')}>
+                {gitVersion.error ??
+                  (gitVersion.version &&
+                    `Git ${gitVersion.version.major}.${gitVersion.version.minor}.${gitVersion.version.patch}`)}
+              </span>
+            )}
+          </label>
+        </PopoverContent>
+      )}
+    </Popover>
+  )
+}
+
+const NumberSetting = ({
+  label,
+  value,
+  onChange,
+}: { label: string; value: number; onChange: (value: number) => void }) => (
+  <label className="flex flex-col gap-1">
+    <span className="text-foreground/70">{label}</span>
+    <input
+      type="number"
+      min={0}
+      className="bg-foreground/10 rounded-md h-8 px-2 outline-hidden border-none"
+      defaultValue={value}
+      onBlur={(e) => {
+        const newValue = Number.parseInt(e.target.value)
+        if (!Number.isNaN(newValue) && newValue !== value) onChange(newValue)
+      }}
+    />
+  </label>
+)
@@ -4,14 +4,24 @@ import { Tooltip, TooltipTrigger } from '@radix-ui/react-tooltip'
 import { useQueryClient } from '@tanstack/react-query'
 import { FilterIcon, LogsIcon, PencilIcon, SearchIcon } from 'lucide-react'
 import { useEffect, useState } from 'react'
-import { events, type GitCommandLog as GitCommandLogType, type GitCommandType, commands } from '../../bindings'
+import {
+  events,
+  type GitCommandLog as GitCommandLogType,
+  type GitCommandLogPage,
+  type GitCommandType,
+  type GitProgress,
+  commands,
+} from '../../bindings'
 import { useOpenRepository } from '../../data/useOpenRepository'
+import { cn } from '../../utils/cn'
 import { formatDate } from '../../utils/formatDate'
 import { useCommandQuery } from '../../utils/useCommandQuery'
 import { PopoverContent } from '../UI/Popover'
 import { SelectContent, SelectItem, SelectTrigger } from '../UI/Select'
 import { TooltipContent } from '../UI/Tooltip'
 
+const PAGE_SIZE = 100
+
 export const GitCommandLog = () => {
   const queryClient = useQueryClient()
   const [isOpen, setIsOpen] = useState(false)
@@ -20,6 +30,7 @@ export const GitCommandLog = () => {
   const [latestCommand, setLatestCommand] = useState<GitCommandLogType>()
   useEffect(() => {
     const unlisten = events.gitCommandEvent.listen(({ payload }) => {
+      setProgress(undefined)
       if (filter === 'all' || payload.command_type === filter) setLatestCommand(payload)
     })
     return () => {
@@ -27,17 +38,42 @@ export const GitCommandLog = () => {
     }
   }, [filter])
 
+  // Progress of a long running command, cleared when the command finishes
+  const [progress, setProgress] = useState<GitProgress>()
+  useEffect(() => {
+    const unlisten = events.gitProgressEvent.listen(({ payload }) => setProgress(payload.progress))
+    return () => {
+      unlisten.then((f) => f())
+    }
+  }, [])
+
   const openRepository = useOpenRepository()
 
-  const { data: log, refetch } = useCommandQuery({
-    queryKey: ['gitCommandLog', openRepository?.id],
-    queryFn: () => commands.getGitCommandLog(filter === 'all' ? null : filter),
+  const [search, setSearch] = useState('')
+
+  const { data: firstPage } = useCommandQuery({
+    queryKey: ['gitCommandLog', openRepository?.id, filter, search],
+    queryFn: () => commands.getGitCommandLog(filter === 'all' ? null : filter, search || null, null, PAGE_SIZE),
     enabled: Boolean(isOpen && openRepository),
   })
 
+  // Older pages loaded with "Load more", reset whenever the first page changes
+  const [olderPages, setOlderPages] = useState<GitCommandLogPage[]>([])
   useEffect(() => {
-    refetch()
-  }, [filter])
+    setOlderPages([])
+  }, [firstPage])
+
+  const lastPage = olderPages.at(-1) ?? firstPage
+  const loadMore = async () => {
+    if (!lastPage?.next_cursor) return
+    const page = await commands.getGitCommandLog(
+      filter === 'all' ? null : filter,
+      search || null,
+      lastPage.next_cursor,
+      PAGE_SIZE,
+    )
+    if (page.status === 'ok') setOlderPages((pages) => [...pages, page.data])
+  }
 
   return (
     <Popover
@@ -59,7 +95,7 @@ export const GitCommandLog = () => {
             <TooltipContent>Git command log</TooltipContent>
           </Tooltip>
           <span className="font-mono text-xs text-foreground/70 whitespace-nowrap overflow-hidden text-ellipsis ml-1 mr-3">
-            {latestCommand?.command ?? 'No commands run'}
+            {progress ? formatProgress(progress) : (latestCommand?.command ?? 'No commands run')}
           </span>
         </button>
       </PopoverTrigger>
@@ -67,6 +103,12 @@ export const GitCommandLog = () => {
       <PopoverContent className="max-h-[min(400px,_var(--radix-popover-content-available-height))] w-[var(--radix-popover-content-available-width)] p-0 flex flex-col">
         <div className="border-b border-foreground/10 pl-2 pr-1 py-1 flex items-center gap-2">
           <span className="text-sm font-semibold text-foreground/70 mr-auto">Git command log</span>
+          <input
+            className="bg-foreground/10 rounded-md h-7 px-2 text-sm outline-hidden border-none w-[170px]"
+            placeholder="Search commands..."
+            value={search}
+            onChange={(e) => setSearch(e.target.value)}
+          />
           <FilterIcon className="h-4 w-4" />
           <Select
             value={filter}
@@ -89,10 +131,14 @@ export const GitCommandLog = () => {
         </div>
 
         <div className="overflow-auto flex-1 p-2">
-          {log?.flatMap((item) => {
-            if (filter !== 'all' && filter !== item.command_type) return []
-            return [<LogItem key={item.id} item={item} />]
-          })}
+          {[firstPage, ...olderPages].flatMap((page) =>
+            (page?.entries ?? []).map((item) => <LogItem key={item.id} item={item} />),
+          )}
+          {lastPage?.next_cursor && (
+            <button type="button" className="text-sm text-foreground/70 hover:text-foreground mt-1" onClick={loadMore}>
+              Load more
+            </button>
+          )}
         </div>
       </PopoverContent>
     </Popover>
@@ -107,7 +153,25 @@ const LogItem = ({ item }: { item: GitCommandLogType }) => {
       </span>
       {item.command_type === 'Query' && <SearchIcon className="h-3 w-3 shrink-0" />}
       {item.command_type === 'Mutation' && <PencilIcon className="h-3 w-3 shrink-0" />}
-      <span>{item.command}</span>
+      <span title={item.progress ?? undefined} className={cn(item.outcome !== 'Success' && 'text-error')}>
+        {item.command}
+      </span>
+      {item.outcome === 'Cancelled' && <span className="text-warn">cancelled</span>}
+      {item.outcome === 'TimedOut' && <span className="text-error">timed out</span>}
+      {item.outcome === 'Failed' && (
+        <span className="text-error" title={item.stderr ?? undefined}>
+          exited with {item.exit_code ?? 'error'}
+        </span>
+      )}
+      <span className="text-foreground/60" title={item.working_directory ?? undefined}>
+        {item.duration_ms}ms
+      </span>
     </div>
   )
 }
+
+const formatProgress = (progress: GitProgress) => {
+  if (progress.percentage !== null) return `${progress.phase}: ${progress.percentage}%`
+  if (progress.current !== null) return `${progress.phase}: ${progress.current}`
+  return progress.phase
+}
@@ -0,0 +1,126 @@
+import { Popover, PopoverTrigger } from '@radix-ui/react-popover'
+import { useQueryClient } from '@tanstack/react-query'
+import { PackageIcon, PowerOffIcon, RefreshCwIcon } from 'lucide-react'
+import { useState } from 'react'
+import { type Submodule, commands } from '../../bindings'
+import { useOpenRepository } from '../../data/useOpenRepository'
+import { cn } from '../../utils/cn'
+import { useCommandMutation } from '../../utils/useCommandMutation'
+import { useCommandQuery } from '../../utils/useCommandQuery'
+import { IconButton } from '../UI/IconButton'
+import { PopoverContent } from '../UI/Popover'
+
+export const Submodules = () => {
+  const openRepository = useOpenRepository()
+  const queryClient = useQueryClient()
+  const [isOpen, setIsOpen] = useState(false)
+
+  const { data: submodules } = useCommandQuery({
+    queryKey: ['submodules', openRepository?.id],
+    enabled: isOpen && Boolean(openRepository),
+    queryFn: commands.getSubmodules,
+  })
+
+  const invalidate = () =>
+    Promise.all([
+      queryClient.invalidateQueries({ queryKey: ['submodules'] }),
+      queryClient.invalidateQueries({ queryKey: ['status'] }),
+    ])
+
+  const updateAll = useCommandMutation({
+    mutationFn: () => commands.updateSubmodules([], { init: true, recursive: true, remote: false }),
+    onSuccess: invalidate,
+  })
+  const syncAll = useCommandMutation({
+    mutationFn: () => commands.syncSubmodules([], true),
+    onSuccess: invalidate,
+  })
+
+  return (
+    <Popover open={isOpen} onOpenChange={setIsOpen}>
+      <PopoverTrigger asChild>
+        <IconButton tooltip="Submodules" disabled={!openRepository}>
+          <PackageIcon />
+        </IconButton>
+      </PopoverTrigger>
+
+      <PopoverContent align="end" className="w-[420px] flex flex-col gap-3 text-sm">
+        {submodules && !submodules.length && <p className="text-foreground/60">No submodules</p>}
+
+        <ul className="flex flex-col gap-1 max-h-[300px] overflow-auto">
+          {submodules?.map((submodule) => (
+            <SubmoduleItem key={submodule.name} submodule={submodule} onChange={invalidate} />
+          ))}
+        </ul>
+
+        {Boolean(submodules?.length) && (
+          <div className="flex gap-2 justify-end">
+            <button
+              type="button"
+              className="rounded-md h-8 px-3 bg-foreground/10 hover:enabled:bg-foreground/20 disabled:opacity-50"
+              disabled={syncAll.isPending}
+              onClick={() => syncAll.mutate()}
+            >
+              Sync URLs
+            </button>
+            <button
+              type="button"
+              className="rounded-md h-8 px-3 bg-foreground/10 hover:enabled:bg-foreground/20 disabled:opacity-50"
+              disabled={updateAll.isPending}
+              onClick={() => updateAll.mutate()}
+            >
+              {updateAll.isPending ? 'Updating...' : 'Init and update all'}
+            </button>
+          </div>
+        )}
+      </PopoverContent>
+    </Popover>
+  )
+}
+
+const SubmoduleItem = ({ submodule, onChange }: { submodule: Submodule; onChange: () => Promise<unknown> }) => {
+  const isInitialized = submodule.checked_out_commit !== null
+  const isMoved = isInitialized && submodule.checked_out_commit !== submodule.recorded_commit
+
+  const update = useCommandMutation({
+    mutationFn: () => commands.updateSubmodules([submodule.path], { init: true, recursive: true, remote: false }),
+    onSuccess: onChange,
+  })
+  const deinit = useCommandMutation({
+    mutationFn: () => commands.deinitSubmodules([submodule.path], false),
+    onSuccess: onChange,
+  })
+
+  return (
+    <li className="flex items-center gap-2">
+      <div className="flex flex-col min-w-0 mr-auto">
+        <span className="font-mono truncate" title={submodule.url ?? undefined}>
+          {submodule.path}
+        </span>
+        <span className={cn('text-xs truncate', isMoved ? 'text-warn' : 'text-foreground/60')}>
+          {!isInitialized
+            ? 'Not initialized'
+            : isMoved
+              ? `${submodule.recorded_commit?.slice(0, 7)} recorded, ${submodule.checked_out_commit?.slice(0, 7)} checked out`
+              : submodule.recorded_commit?.slice(0, 7)}
+          {submodule.change.modified_content && ' · modified'}
+          {submodule.change.untracked_content && ' · untracked files'}
+        </span>
+      </div>
+
+      <IconButton
+        tooltip={isInitialized ? 'Check out recorded commit' : 'Initialize and check out'}
+        size="sm"
+        disabled={update.isPending}
+        onClick={() => update.mutate()}
+      >
+        <RefreshCwIcon className={cn(update.isPending && 'animate-spin')} />
+      </IconButton>
+      {isInitialized && (
+        <IconButton tooltip="Deinitialize" size="sm" disabled={deinit.isPending} onClick={() => deinit.mutate()}>
+          <PowerOffIcon />
+        </IconButton>
+      )}
+    </li>
+  )
+}
@@ -6,14 +6,18 @@ import {
   RedoIcon,
   RefreshCwIcon,
   SearchIcon,
-  SettingsIcon,
   UndoIcon,
 } from 'lucide-react'
-import { commands } from '../../bindings'
+import { useEffect } from 'react'
+import { commands, events } from '../../bindings'
+import { useOpenRepository } from '../../data/useOpenRepository'
 import { cn } from '../../utils/cn'
 import { useCommandMutation } from '../../utils/useCommandMutation'
 import { RepositorySelector } from '../RepositorySelector'
+import { Settings } from '../Settings'
+import { Submodules } from '../Submodules'
 import { IconButton } from '../UI/IconButton'
+import { Worktrees } from '../Worktrees'
 
 export const Toolbar = () => {
   const queryClient = useQueryClient()
@@ -27,12 +31,32 @@ export const Toolbar = () => {
       ]),
   })
 
+  // Refresh after the open repository is fetched in the background
+  const openRepository = useOpenRepository()
+  useEffect(() => {
+    const unlisten = events.repositoryFetchedEvent.listen(({ payload }) => {
+      if (payload.repository_id !== openRepository?.id) return
+      queryClient.invalidateQueries({ queryKey: ['branches'] })
+      queryClient.invalidateQueries({ queryKey: ['graph'] })
+    })
+    return () => {
+      unlisten.then((f) => f())
+    }
+  }, [openRepository?.id])
+
+  const cancelFetch = async () => {
+    const running = await commands.getRunningGitCommands()
+    if (running.status === 'error') return
+    for (const command of running.data) {
+      if (command.command.startsWith('fetch ')) await commands.cancelGitCommand(command.id)
+    }
+  }
+
   return (
     <nav className="flex gap-3 items-center">
       <IconButton
-        tooltip={fetchAll.isPending ? 'Fetching...' : 'Fetch'}
-        onClick={() => fetchAll.mutate()}
-        disabled={fetchAll.isPending}
+        tooltip={fetchAll.isPending ? 'Cancel fetch' : 'Fetch'}
+        onClick={() => (fetchAll.isPending ? cancelFetch() : fetchAll.mutate())}
       >
         <RefreshCwIcon className={cn(fetchAll.isPending && 'animate-spin')} />
       </IconButton>
@@ -67,9 +91,9 @@ export const Toolbar = () => {
       <div className="flex-1" />
 
       <RepositorySelector />
-      <IconButton tooltip="Settings" disabled>
-        <SettingsIcon />
-      </IconButton>
+      <Worktrees />
+      <Submodules />
+      <Settings />
     </nav>
   )
 }
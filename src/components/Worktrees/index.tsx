@@ -0,0 +1,201 @@
+import { Popover, PopoverTrigger } from '@radix-ui/react-popover'
+import { useQueryClient } from '@tanstack/react-query'
+import { FolderTreeIcon, LockIcon, LockOpenIcon, PlusIcon, Trash2Icon } from 'lucide-react'
+import { useState } from 'react'
+import { type Worktree, commands } from '../../bindings'
+import { useOpenRepository } from '../../data/useOpenRepository'
+import { useCommandMutation } from '../../utils/useCommandMutation'
+import { useCommandQuery } from '../../utils/useCommandQuery'
+import { IconButton } from '../UI/IconButton'
+import { PopoverContent } from '../UI/Popover'
+
+export const Worktrees = () => {
+  const openRepository = useOpenRepository()
+  const queryClient = useQueryClient()
+  const [isOpen, setIsOpen] = useState(false)
+
+  const { data: worktrees } = useCommandQuery({
+    queryKey: ['worktrees', openRepository?.id],
+    enabled: isOpen && Boolean(openRepository),
+    queryFn: commands.getWorktrees,
+  })
+  const { data: repositories } = useCommandQuery({
+    queryKey: ['repositories'],
+    queryFn: commands.getRepositories,
+  })
+
+  const invalidate = () =>
+    Promise.all([
+      queryClient.invalidateQueries({ queryKey: ['worktrees'] }),
+      queryClient.invalidateQueries({ queryKey: ['branches'] }),
+      queryClient.invalidateQueries({ queryKey: ['repositories'] }),
+    ])
+
+  const pruneWorktrees = useCommandMutation({
+    mutationFn: commands.pruneWorktrees,
+    onSuccess: invalidate,
+  })
+
+  return (
+    <Popover open={isOpen} onOpenChange={setIsOpen}>
+      <PopoverTrigger asChild>
+        <IconButton tooltip="Worktrees" disabled={!openRepository}>
+          <FolderTreeIcon />
+        </IconButton>
+      </PopoverTrigger>
+
+      <PopoverContent align="end" className="w-[420px] flex flex-col gap-3 text-sm">
+        <ul className="flex flex-col gap-1 max-h-[300px] overflow-auto">
+          {worktrees?.map((worktree) => (
+            <WorktreeItem
+              key={worktree.path}
+              worktree={worktree}
+              isRegistered={Boolean(repositories?.some((repo) => repo.local_path === worktree.path))}
+              onChange={invalidate}
+            />
+          ))}
+        </ul>
+
+        {worktrees?.some((worktree) => worktree.prunable !== null) && (
+          <button
+            type="button"
+            className="self-start rounded-md h-8 px-3 bg-foreground/10 hover:enabled:bg-foreground/20 disabled:opacity-50"
+            disabled={pruneWorktrees.isPending}
+            onClick={() => pruneWorktrees.mutate()}
+          >
+            Prune missing worktrees
+          </button>
+        )}
+
+        <AddWorktree onAdded={invalidate} />
+      </PopoverContent>
+    </Popover>
+  )
+}
+
+const WorktreeItem = ({
+  worktree,
+  isRegistered,
+  onChange,
+}: { worktree: Worktree; isRegistered: boolean; onChange: () => Promise<unknown> }) => {
+  const isLocked = worktree.locked !== null
+
+  const toggleLock = useCommandMutation({
+    mutationFn: () => (isLocked ? commands.unlockWorktree(worktree.path) : commands.lockWorktree(worktree.path, null)),
+    onSuccess: onChange,
+  })
+  const removeWorktree = useCommandMutation({
+    mutationFn: () => commands.removeWorktree(worktree.path, isLocked),
+    onSuccess: onChange,
+  })
+  const registerWorktree = useCommandMutation({
+    mutationFn: () => commands.registerWorktree(worktree.path),
+    onSuccess: onChange,
+  })
+
+  return (
+    <li className="flex items-center gap-2">
+      <div className="flex flex-col min-w-0 mr-auto">
+        <span className="font-mono truncate" title={worktree.path}>
+          {worktree.path}
+        </span>
+        <span className="text-xs text-foreground/60 truncate">
+          {worktree.bare ? 'bare' : (worktree.branch ?? `detached at ${worktree.head?.slice(0, 7)}`)}
+          {worktree.main && ' · main worktree'}
+          {isLocked && ` · locked${worktree.locked ? `: ${worktree.locked}` : ''}`}
+          {worktree.prunable !== null && ' · missing'}
+        </span>
+      </div>
+
+      {!worktree.main && !worktree.bare && !isRegistered && (
+        <IconButton
+          tooltip="Add to repositories"
+          size="sm"
+          disabled={registerWorktree.isPending}
+          onClick={() => registerWorktree.mutate()}
+        >
+          <PlusIcon />
+        </IconButton>
+      )}
+      {!worktree.main && (
+        <>
+          <IconButton
+            tooltip={isLocked ? 'Unlock' : 'Lock'}
+            size="sm"
+            disabled={toggleLock.isPending}
+            onClick={() => toggleLock.mutate()}
+          >
+            {isLocked ? <LockOpenIcon /> : <LockIcon />}
+          </IconButton>
+          <IconButton
+            tooltip="Remove worktree"
+            size="sm"
+            disabled={removeWorktree.isPending}
+            onClick={() => removeWorktree.mutate()}
+          >
+            <Trash2Icon />
+          </IconButton>
+        </>
+      )}
+    </li>
+  )
+}
+
+const AddWorktree = ({ onAdded }: { onAdded: () => Promise<unknown> }) => {
+  const [path, setPath] = useState('')
+  const [branch, setBranch] = useState('')
+  const [createBranch, setCreateBranch] = useState(false)
+  const [register, setRegister] = useState(true)
+
+  const addWorktree = useCommandMutation({
+    mutationFn: () =>
+      commands.addWorktree(path.trim(), branch.trim(), {
+        create_branch: createBranch,
+        start_point: null,
+        register,
+      }),
+    onSuccess: async () => {
+      await onAdded()
+      setPath('')
+      setBranch('')
+    },
+  })
+
+  return (
+    <form
+      className="flex flex-col gap-2 border-t border-foreground/10 pt-3"
+      onSubmit={(e) => {
+        e.preventDefault()
+        addWorktree.mutate()
+      }}
+    >
+      <input
+        className="bg-foreground/10 rounded-md h-8 px-2 outline-hidden border-none font-mono"
+        placeholder="Path, e.g. ../repo-feature"
+        value={path}
+        onChange={(e) => setPath(e.target.value)}
+      />
+      <input
+        className="bg-foreground/10 rounded-md h-8 px-2 outline-hidden border-none font-mono"
+        placeholder="Branch"
+        value={branch}
+        onChange={(e) => setBranch(e.target.value)}
+      />
+      <label className="flex items-center gap-2">
+        <input type="checkbox" checked={createBranch} onChange={(e) => setCreateBranch(e.target.checked)} />
+        <span className="text-foreground/70">Create a new branch from HEAD</span>
+      </label>
+      <label className="flex items-center gap-2">
+        <input type="checkbox" checked={register} onChange={(e) => setRegister(e.target.checked)} />
+        <span className="text-foreground/70">Add to repositories</span>
+      </label>
+      <button
+        type="submit"
+        className="self-end rounded-md h-8 px-3 bg-foreground/10 hover:enabled:bg-foreground/20 disabled:opacity-50"
+        disabled={!path.trim() || !branch.trim() || addWorktree.isPending}
+      >
+        Add worktree
+      </button>
+    </form>
+  )
+}
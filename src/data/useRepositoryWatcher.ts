@@ -0,0 +1,47 @@
+import { useQueryClient } from '@tanstack/react-query'
+import { useEffect } from 'react'
+import { commands, events } from '../bindings'
+import { toast } from '../components/Toaster'
+import { useCommandQuery } from '../utils/useCommandQuery'
+import { useOpenRepository } from './useOpenRepository'
+
+/** Refresh data when the open repository is changed outside the app */
+export const useRepositoryWatcher = () => {
+  const queryClient = useQueryClient()
+  const openRepository = useOpenRepository()
+
+  useEffect(() => {
+    const invalidate = (repositoryId: number, queryKeys: string[]) => {
+      if (repositoryId !== openRepository?.id) return
+      for (const key of queryKeys) queryClient.invalidateQueries({ queryKey: [key] })
+    }
+
+    const unlisteners = [
+      events.workingTreeChangedEvent.listen(({ payload }) => invalidate(payload.repository_id, ['status'])),
+      events.refsChangedEvent.listen(({ payload }) => invalidate(payload.repository_id, ['branches', 'graph'])),
+      events.headMovedEvent.listen(({ payload }) => invalidate(payload.repository_id, ['branches', 'graph', 'status'])),
+      events.repositoryWatchFailedEvent.listen(({ payload }) =>
+        invalidate(payload.repository_id, ['repositoryWatchFailure']),
+      ),
+    ]
+    return () => {
+      for (const unlisten of unlisteners) unlisten.then((f) => f())
+    }
+  }, [openRepository?.id])
+
+  // Includes failures from before the app loaded, like watching the repository that was open at startup
+  const { data: watchFailure } = useCommandQuery({
+    queryKey: ['repositoryWatchFailure', openRepository?.id],
+    enabled: Boolean(openRepository),
+    queryFn: commands.getRepositoryWatchFailure,
+  })
+
+  useEffect(() => {
+    if (watchFailure && watchFailure.repository_id === openRepository?.id)
+      toast({
+        variant: 'warning',
+        title: "Changes made outside the app won't show up",
+        children: watchFailure.message,
+      })
+  }, [watchFailure?.message])
+}
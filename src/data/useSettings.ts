@@ -0,0 +1,24 @@
+import { useQueryClient } from '@tanstack/react-query'
+import { useEffect } from 'react'
+import { commands, events } from '../bindings'
+import { useCommandQuery } from '../utils/useCommandQuery'
+
+export const useSettings = () => {
+  const queryClient = useQueryClient()
+
+  const { data: settings } = useCommandQuery({
+    queryKey: ['settings'],
+    queryFn: commands.getSettings,
+  })
+
+  useEffect(() => {
+    const unlisten = events.settingsChangedEvent.listen(({ payload }) => {
+      queryClient.setQueryData(['settings'], payload)
+    })
+    return () => {
+      unlisten.then((f) => f())
+    }
+  }, [queryClient])
+
+  return settings
+}
@@ -1,7 +1,19 @@
 import { atom } from 'jotai'
+import type { DiffLayout, MergeDiffMode } from './bindings'
 
 export const selectedCommitHashAtom = atom<string | null>(null)
 
 export const selectedFileIdAtom = atom<string | null>(null)
 
+export const diffLayoutAtom = atom<DiffLayout>('Unified')
+
+export const mergeDiffModeAtom = atom<MergeDiffMode>({ Parent: 1 })
+
+/** Regular commits can only be diffed against their first parent */
+export const mergeDiffModeForCommit = (mode: MergeDiffMode, parentCount: number): MergeDiffMode => {
+  if (parentCount < 2) return { Parent: 1 }
+  if (typeof mode === 'object' && mode.Parent > parentCount) return { Parent: 1 }
+  return mode
+}
+
 export const calculateFileId = (commitHash: string, path: string) => `${commitHash}-${path}`